@@ -2,6 +2,8 @@
 mod sysinfo_ffi;
 mod system_properties;
 
+use crate::capabilities::{Capability, Prerequisite, ReadoutKind};
+use crate::command;
 use crate::extra;
 use crate::shared;
 use crate::traits::*;
@@ -9,18 +11,17 @@ use itertools::Itertools;
 use std::ffi::{CStr, CString};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
 use sysinfo_ffi::sysinfo;
 use system_properties::getprop;
 
 impl From<std::str::Utf8Error> for ReadoutError {
     fn from(e: std::str::Utf8Error) -> Self {
-        ReadoutError::Other(e.to_string())
+        ReadoutError::from_source(ReadoutErrorKind::ParseError, e)
     }
 }
 impl From<std::num::ParseFloatError> for ReadoutError {
     fn from(e: std::num::ParseFloatError) -> Self {
-        ReadoutError::Other(e.to_string())
+        ReadoutError::from_source(ReadoutErrorKind::ParseError, e)
     }
 }
 
@@ -41,6 +42,7 @@ pub struct AndroidMemoryReadout {
 pub struct AndroidProductReadout;
 pub struct AndroidPackageReadout;
 pub struct AndroidNetworkReadout;
+pub struct AndroidSensorReadout;
 
 impl BatteryReadout for AndroidBatteryReadout {
     fn new() -> Self {
@@ -70,7 +72,10 @@ impl BatteryReadout for AndroidBatteryReadout {
         let status_text = extra::pop_newline(fs::read_to_string(bat_path)?).to_lowercase();
         match &status_text[..] {
             "charging" => Ok(BatteryState::Charging),
-            "discharging" | "full" => Ok(BatteryState::Discharging),
+            "discharging" => Ok(BatteryState::Discharging),
+            "full" => Ok(BatteryState::Full),
+            "not charging" => Ok(BatteryState::NotCharging),
+            "unknown" => Ok(BatteryState::Unknown),
             s => Err(ReadoutError::Other(format!(
                 "Got unexpected value '{}' from {}.",
                 s,
@@ -231,9 +236,7 @@ impl GeneralReadout for AndroidGeneralReadout {
             (Some(hardware), _, _) => Ok(hardware),
             (_, Some(model), _) => Ok(model),
             (_, _, Some(processor)) => Ok(processor),
-            (_, _, _) => Err(ReadoutError::Other(String::from(
-                "Failed to get processor model name",
-            ))),
+            (_, _, _) => shared::cpu_model_name(),
         }
     }
 
@@ -264,6 +267,31 @@ impl GeneralReadout for AndroidGeneralReadout {
         }
     }
 
+    fn load_average(&self) -> Result<LoadAverage, ReadoutError> {
+        // /proc/loadavg can't be read by apps on recent releases of Android
+        if let Ok(load) = shared::load_average_at(Path::new("/")) {
+            return Ok(load);
+        }
+
+        let mut info = self.sysinfo;
+        let info_ptr: *mut sysinfo = &mut info;
+        let ret = unsafe { sysinfo(info_ptr) };
+        if ret == -1 {
+            return Err(ReadoutError::Other(
+                "Failed to get system statistics".to_string(),
+            ));
+        }
+
+        let f_load = 1f64 / (1 << libc::SI_LOAD_SHIFT) as f64;
+        Ok(LoadAverage {
+            one: info.loads[0] as f64 * f_load,
+            five: info.loads[1] as f64 * f_load,
+            fifteen: info.loads[2] as f64 * f_load,
+            runnable_tasks: None,
+            total_tasks: Some(info.procs as u64),
+        })
+    }
+
     fn uptime(&self) -> Result<usize, ReadoutError> {
         let mut info = self.sysinfo;
         let info_ptr: *mut sysinfo = &mut info;
@@ -342,11 +370,13 @@ impl MemoryReadout for AndroidMemoryReadout {
     }
 
     fn cached(&self) -> Result<u64, ReadoutError> {
-        Ok(shared::get_meminfo_value("Cached"))
+        Ok(shared::read_meminfo()?.cached)
     }
 
     fn reclaimable(&self) -> Result<u64, ReadoutError> {
-        Ok(shared::get_meminfo_value("SReclaimable"))
+        shared::read_meminfo()?
+            .slab_reclaimable
+            .ok_or(ReadoutError::MetricNotAvailable)
     }
 
     fn used(&self) -> Result<u64, ReadoutError> {
@@ -370,6 +400,10 @@ impl MemoryReadout for AndroidMemoryReadout {
     fn swap_used(&self) -> Result<u64, ReadoutError> {
         return Err(ReadoutError::NotImplemented);
     }
+
+    fn breakdown(&self) -> Result<MemoryBreakdown, ReadoutError> {
+        shared::read_meminfo()
+    }
 }
 
 impl ProductReadout for AndroidProductReadout {
@@ -426,16 +460,9 @@ impl AndroidPackageReadout {
     /// Returns the number of installed apps for the system
     /// Includes all apps ( user + system )
     fn count_pm() -> Option<usize> {
-        let pm_output = Command::new("pm")
-            .args(["list", "packages"])
-            .stdout(Stdio::piped())
-            .output()
-            .unwrap();
+        let pm_output = command::run("pm", ["list", "packages"]);
 
-        extra::count_lines(
-            String::from_utf8(pm_output.stdout)
-                .expect("ERROR: \"pm list packages\" output was not valid UTF-8"),
-        )
+        extra::count_lines(pm_output.ok()?)
     }
     /// Return the number of installed packages for systems
     /// that have `dpkg` installed.
@@ -492,3 +519,42 @@ impl NetworkReadout for AndroidNetworkReadout {
         Err(ReadoutError::NotImplemented)
     }
 }
+
+impl SensorReadout for AndroidSensorReadout {
+    fn new() -> Self {
+        AndroidSensorReadout
+    }
+}
+
+/// The metrics implemented by this backend.
+pub(crate) const CAPABILITIES: &[Capability] = &[
+    Capability::new(ReadoutKind::Battery, "percentage"),
+    Capability::new(ReadoutKind::Battery, "status"),
+    Capability::new(ReadoutKind::Kernel, "os_release"),
+    Capability::new(ReadoutKind::Kernel, "os_type"),
+    Capability::new(ReadoutKind::Kernel, "pretty_kernel"),
+    Capability::new(ReadoutKind::Memory, "total"),
+    Capability::new(ReadoutKind::Memory, "free"),
+    Capability::new(ReadoutKind::Memory, "buffers"),
+    Capability::new(ReadoutKind::Memory, "cached"),
+    Capability::new(ReadoutKind::Memory, "reclaimable"),
+    Capability::new(ReadoutKind::Memory, "used"),
+    Capability::new(ReadoutKind::Memory, "breakdown"),
+    Capability::new(ReadoutKind::General, "username"),
+    Capability::new(ReadoutKind::General, "hostname"),
+    Capability::new(ReadoutKind::General, "shell"),
+    Capability::new(ReadoutKind::General, "cpu_model_name"),
+    Capability::new(ReadoutKind::General, "cpu_usage"),
+    Capability::new(ReadoutKind::General, "load_average"),
+    Capability::new(ReadoutKind::General, "cpu_physical_cores"),
+    Capability::new(ReadoutKind::General, "cpu_cores"),
+    Capability::new(ReadoutKind::General, "uptime"),
+    Capability::new(ReadoutKind::General, "machine"),
+    Capability::new(ReadoutKind::General, "os_name"),
+    Capability::new(ReadoutKind::Product, "vendor"),
+    Capability::new(ReadoutKind::Product, "family"),
+    Capability::new(ReadoutKind::Product, "product"),
+    Capability::new(ReadoutKind::Package, "count_pkgs")
+        .requires(&[Prerequisite::Program("pm"), Prerequisite::Program("dpkg")]),
+    Capability::new(ReadoutKind::Network, "logical_address"),
+];
@@ -0,0 +1,97 @@
+//! This module describes which metrics the compiled backend implements, so that callers can find
+//! out what is supported on the current target without calling every readout and checking for
+//! [`ReadoutError::NotImplemented`](crate::traits::ReadoutError::NotImplemented).
+//!
+//! ```
+//! use libmacchina::capabilities::ReadoutKind;
+//! use libmacchina::Readouts;
+//!
+//! for capability in Readouts::capabilities() {
+//!     println!("{:?}::{}", capability.readout, capability.metric);
+//! }
+//!
+//! let gpus = Readouts::supports(ReadoutKind::General, "gpus");
+//! ```
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// The readout a metric belongs to, one for each trait in [`traits`](crate::traits).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum ReadoutKind {
+    Battery,
+    Kernel,
+    Memory,
+    General,
+    Product,
+    Package,
+    Network,
+    Sensor,
+}
+
+/// Something a metric needs at runtime, in addition to being implemented by the backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum Prerequisite {
+    /// A program that must be found in __PATH__, e.g. `xprop`.
+    Program(&'static str),
+
+    /// A shared library that is loaded at runtime, e.g. `librpm`.
+    Library(&'static str),
+
+    /// A data file that must be installed, e.g. `pci.ids`.
+    File(&'static str),
+
+    /// A running X11 server the readout can connect to.
+    X11Server,
+
+    /// A running Wayland compositor the readout can connect to.
+    WaylandCompositor,
+}
+
+impl std::fmt::Display for Prerequisite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Prerequisite::Program(p) => write!(f, "{p}"),
+            Prerequisite::Library(l) => write!(f, "{l}"),
+            Prerequisite::File(p) => write!(f, "{p}"),
+            Prerequisite::X11Server => write!(f, "an X11 server"),
+            Prerequisite::WaylandCompositor => write!(f, "a Wayland compositor"),
+        }
+    }
+}
+
+/// A metric that is implemented by the compiled backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Capability {
+    /// The readout the metric belongs to.
+    pub readout: ReadoutKind,
+
+    /// The metric, named after the trait method that returns it, e.g. `percentage`.
+    pub metric: &'static str,
+
+    /// The runtime prerequisites of the metric. When there is more than one, the metric is
+    /// available if any of them is met, and more complete the more of them are met.
+    pub prerequisites: &'static [Prerequisite],
+}
+
+impl Capability {
+    /// Describes a metric without any runtime prerequisites.
+    pub const fn new(readout: ReadoutKind, metric: &'static str) -> Self {
+        Capability {
+            readout,
+            metric,
+            prerequisites: &[],
+        }
+    }
+
+    /// Sets the runtime prerequisites of the metric.
+    pub const fn requires(self, prerequisites: &'static [Prerequisite]) -> Self {
+        Capability {
+            prerequisites,
+            ..self
+        }
+    }
+}
@@ -0,0 +1,424 @@
+//! This module provides [`Collector`], which gathers a chosen set of metrics concurrently and
+//! gives each of them its own deadline.
+//!
+//! Some readouts are expensive or may hang, e.g. detecting the window manager spawns `xprop`
+//! and counting packages might load `librpm`. Every metric is therefore read on a thread of its
+//! own, and a metric that does not finish in time is reported as a
+//! [`ReadoutErrorKind::Timeout`] failure instead of holding up the others.
+//!
+//! ```no_run
+//! use libmacchina::capabilities::ReadoutKind;
+//! use libmacchina::collect::Collector;
+//! use std::time::Duration;
+//!
+//! let snapshot = Collector::new()
+//!     .metric(ReadoutKind::General, "hostname", Duration::from_millis(10))
+//!     .metric(ReadoutKind::General, "window_manager", Duration::from_millis(40))
+//!     .metric(ReadoutKind::Package, "count_pkgs", Duration::from_millis(40))
+//!     .collect();
+//!
+//! println!("{:?}", snapshot.general.window_manager);
+//! ```
+
+use crate::capabilities::ReadoutKind;
+use crate::snapshot::{SystemSnapshot, CPU_STATS_INTERVAL};
+use crate::traits::*;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A function that stores the result of a metric in a snapshot.
+type Apply = Box<dyn FnOnce(&mut SystemSnapshot) + Send>;
+
+/// The environment a metric is read in.
+struct Context {
+    interface: Option<String>,
+    path: PathBuf,
+}
+
+/// Knows how to read a single metric and where to store its result.
+struct Task {
+    run: fn(&Context) -> Apply,
+    fail: fn(&mut SystemSnapshot, ReadoutError),
+}
+
+/// Builds a `Task` that stores `$value` in `$section.$field` of the snapshot.
+macro_rules! task {
+    ($section:ident . $field:ident, |$ctx:ident| $value:expr) => {
+        Task {
+            run: |$ctx| {
+                let value = $value;
+                Box::new(move |snapshot: &mut SystemSnapshot| snapshot.$section.$field = value)
+            },
+            fail: |snapshot, error| snapshot.$section.$field = Err(error),
+        }
+    };
+}
+
+/// Looks up the task for `metric` of `readout`. Metrics are named after the fields of
+/// [`SystemSnapshot`], which match the trait methods that return them.
+fn task(readout: ReadoutKind, metric: &str) -> Option<Task> {
+    use crate::{
+        BatteryReadout as Battery, GeneralReadout as General, KernelReadout as Kernel,
+        MemoryReadout as Memory, NetworkReadout as Network, PackageReadout as Package,
+        ProductReadout as Product, SensorReadout as Sensor,
+    };
+
+    let task = match (readout, metric) {
+        (ReadoutKind::Battery, "percentage") => {
+            task!(battery.percentage, |_c| Battery::new().percentage())
+        }
+        (ReadoutKind::Battery, "status") => task!(battery.status, |_c| Battery::new().status()),
+        (ReadoutKind::Battery, "health") => task!(battery.health, |_c| Battery::new().health()),
+        (ReadoutKind::Battery, "batteries") => {
+            task!(battery.batteries, |_c| Battery::new().batteries())
+        }
+        (ReadoutKind::Battery, "combined_percentage") => {
+            task!(battery.combined_percentage, |_c| Battery::new()
+                .combined_percentage())
+        }
+        (ReadoutKind::Battery, "capacity") => {
+            task!(battery.capacity, |_c| Battery::new().capacity())
+        }
+        (ReadoutKind::Battery, "power") => task!(battery.power, |_c| Battery::new().power()),
+        (ReadoutKind::Battery, "voltage") => task!(battery.voltage, |_c| Battery::new().voltage()),
+        (ReadoutKind::Battery, "time_to_empty") => {
+            task!(battery.time_to_empty, |_c| Battery::new().time_to_empty())
+        }
+        (ReadoutKind::Battery, "time_to_full") => {
+            task!(battery.time_to_full, |_c| Battery::new().time_to_full())
+        }
+        (ReadoutKind::Battery, "ac_online") => {
+            task!(battery.ac_online, |_c| Battery::new().ac_online())
+        }
+        (ReadoutKind::Battery, "peripherals") => {
+            task!(battery.peripherals, |_c| Battery::new().peripherals())
+        }
+        (ReadoutKind::Kernel, "os_release") => {
+            task!(kernel.os_release, |_c| Kernel::new().os_release())
+        }
+        (ReadoutKind::Kernel, "os_type") => task!(kernel.os_type, |_c| Kernel::new().os_type()),
+        (ReadoutKind::Kernel, "pretty_kernel") => {
+            task!(kernel.pretty_kernel, |_c| Kernel::new().pretty_kernel())
+        }
+        (ReadoutKind::Memory, "total") => task!(memory.total, |_c| Memory::new().total()),
+        (ReadoutKind::Memory, "free") => task!(memory.free, |_c| Memory::new().free()),
+        (ReadoutKind::Memory, "buffers") => task!(memory.buffers, |_c| Memory::new().buffers()),
+        (ReadoutKind::Memory, "cached") => task!(memory.cached, |_c| Memory::new().cached()),
+        (ReadoutKind::Memory, "reclaimable") => {
+            task!(memory.reclaimable, |_c| Memory::new().reclaimable())
+        }
+        (ReadoutKind::Memory, "used") => task!(memory.used, |_c| Memory::new().used()),
+        (ReadoutKind::Memory, "swap_total") => {
+            task!(memory.swap_total, |_c| Memory::new().swap_total())
+        }
+        (ReadoutKind::Memory, "swap_free") => {
+            task!(memory.swap_free, |_c| Memory::new().swap_free())
+        }
+        (ReadoutKind::Memory, "swap_used") => {
+            task!(memory.swap_used, |_c| Memory::new().swap_used())
+        }
+        (ReadoutKind::Memory, "breakdown") => {
+            task!(memory.breakdown, |_c| Memory::new().breakdown())
+        }
+        (ReadoutKind::Memory, "pressure") => task!(memory.pressure, |_c| Memory::new().pressure()),
+        (ReadoutKind::Memory, "vm_stats") => task!(memory.vm_stats, |_c| Memory::new().vm_stats()),
+        (ReadoutKind::Memory, "swap") => task!(memory.swap, |_c| Memory::new().swap()),
+        (ReadoutKind::Memory, "limits") => task!(memory.limits, |_c| Memory::new().limits()),
+        (ReadoutKind::Memory, "numa_nodes") => {
+            task!(memory.numa_nodes, |_c| Memory::new().numa_nodes())
+        }
+        (ReadoutKind::Memory, "huge_pages") => {
+            task!(memory.huge_pages, |_c| Memory::new().huge_pages())
+        }
+        (ReadoutKind::General, "backlight") => {
+            task!(general.backlight, |_c| General::new().backlight())
+        }
+        (ReadoutKind::General, "resolution") => {
+            task!(general.resolution, |_c| General::new().resolution())
+        }
+        (ReadoutKind::General, "username") => {
+            task!(general.username, |_c| General::new().username())
+        }
+        (ReadoutKind::General, "hostname") => {
+            task!(general.hostname, |_c| General::new().hostname())
+        }
+        (ReadoutKind::General, "distribution") => {
+            task!(general.distribution, |_c| General::new().distribution())
+        }
+        (ReadoutKind::General, "desktop_environment") => {
+            task!(general.desktop_environment, |_c| General::new()
+                .desktop_environment())
+        }
+        (ReadoutKind::General, "session") => {
+            task!(general.session, |_c| General::new().session())
+        }
+        (ReadoutKind::General, "window_manager") => {
+            task!(general.window_manager, |_c| General::new().window_manager())
+        }
+        (ReadoutKind::General, "terminal") => {
+            task!(general.terminal, |_c| General::new().terminal())
+        }
+        (ReadoutKind::General, "shell") => task!(general.shell, |_c| General::new()
+            .shell(ShellFormat::Relative, ShellKind::Current)),
+        (ReadoutKind::General, "default_shell") => {
+            task!(general.default_shell, |_c| General::new()
+                .shell(ShellFormat::Relative, ShellKind::Default))
+        }
+        (ReadoutKind::General, "cpu_model_name") => {
+            task!(general.cpu_model_name, |_c| General::new().cpu_model_name())
+        }
+        (ReadoutKind::General, "cpu_usage") => {
+            task!(general.cpu_usage, |_c| General::new().cpu_usage())
+        }
+        (ReadoutKind::General, "cpu_stats") => task!(general.cpu_stats, |_c| General::new()
+            .cpu_stats(CPU_STATS_INTERVAL)),
+        (ReadoutKind::General, "cpu_physical_cores") => {
+            task!(general.cpu_physical_cores, |_c| General::new()
+                .cpu_physical_cores())
+        }
+        (ReadoutKind::General, "cpu_cores") => {
+            task!(general.cpu_cores, |_c| General::new().cpu_cores())
+        }
+        (ReadoutKind::General, "uptime") => task!(general.uptime, |_c| General::new().uptime()),
+        (ReadoutKind::General, "machine") => {
+            task!(general.machine, |_c| General::new().machine())
+        }
+        (ReadoutKind::General, "os_name") => {
+            task!(general.os_name, |_c| General::new().os_name())
+        }
+        (ReadoutKind::General, "disk_space") => {
+            task!(general.disk_space, |c| General::new().disk_space(&c.path))
+        }
+        (ReadoutKind::General, "gpus") => task!(general.gpus, |_c| General::new().gpus()),
+        (ReadoutKind::General, "load_average") => {
+            task!(general.load_average, |_c| General::new().load_average())
+        }
+        (ReadoutKind::General, "cpu_frequency") => {
+            task!(general.cpu_frequency, |_c| General::new().cpu_frequency())
+        }
+        (ReadoutKind::General, "cpu_topology") => {
+            task!(general.cpu_topology, |_c| General::new().cpu_topology())
+        }
+        (ReadoutKind::General, "cpu_features") => {
+            task!(general.cpu_features, |_c| General::new().cpu_features())
+        }
+        (ReadoutKind::General, "cpu_vulnerabilities") => {
+            task!(general.cpu_vulnerabilities, |_c| General::new()
+                .cpu_vulnerabilities())
+        }
+        (ReadoutKind::General, "cpu_limits") => {
+            task!(general.cpu_limits, |_c| General::new().cpu_limits())
+        }
+        (ReadoutKind::Product, "vendor") => {
+            task!(product.vendor, |_c| Product::new().vendor())
+        }
+        (ReadoutKind::Product, "family") => {
+            task!(product.family, |_c| Product::new().family())
+        }
+        (ReadoutKind::Product, "product") => {
+            task!(product.product, |_c| Product::new().product())
+        }
+        (ReadoutKind::Package, "count_pkgs") => Task {
+            run: |_c| {
+                let value = Package::new().count_pkgs();
+                Box::new(move |snapshot: &mut SystemSnapshot| snapshot.packages = Ok(value))
+            },
+            fail: |snapshot, error| snapshot.packages = Err(error),
+        },
+        (ReadoutKind::Network, "tx_bytes") => task!(network.tx_bytes, |c| Network::new()
+            .tx_bytes(c.interface.as_deref())),
+        (ReadoutKind::Network, "tx_packets") => {
+            task!(network.tx_packets, |c| Network::new()
+                .tx_packets(c.interface.as_deref()))
+        }
+        (ReadoutKind::Network, "rx_bytes") => task!(network.rx_bytes, |c| Network::new()
+            .rx_bytes(c.interface.as_deref())),
+        (ReadoutKind::Network, "rx_packets") => {
+            task!(network.rx_packets, |c| Network::new()
+                .rx_packets(c.interface.as_deref()))
+        }
+        (ReadoutKind::Network, "logical_address") => {
+            task!(network.logical_address, |c| Network::new()
+                .logical_address(c.interface.as_deref()))
+        }
+        (ReadoutKind::Network, "physical_address") => {
+            task!(network.physical_address, |c| Network::new()
+                .physical_address(c.interface.as_deref()))
+        }
+        (ReadoutKind::Sensor, "chips") => task!(sensors.chips, |_c| Sensor::new().chips()),
+        (ReadoutKind::Sensor, "cpu_temperature") => {
+            task!(sensors.cpu_temperature, |_c| Sensor::new()
+                .cpu_temperature())
+        }
+        _ => return None,
+    };
+
+    Some(task)
+}
+
+/// Gathers a chosen set of metrics concurrently, each with its own deadline.
+///
+/// Every metric is read on a separate thread using freshly created readouts. When a metric
+/// misses its deadline, its field in the resulting [`SystemSnapshot`] is set to a
+/// [`ReadoutErrorKind::Timeout`] failure; the thread reading it is left to finish in the
+/// background, as there is no way to interrupt it. Metrics that were not requested are left
+/// at their default of [`ReadoutError::NotImplemented`].
+pub struct Collector {
+    metrics: Vec<(ReadoutKind, String, Duration)>,
+    interface: Option<String>,
+    path: PathBuf,
+}
+
+impl Collector {
+    /// Creates a collector without any metrics.
+    pub fn new() -> Self {
+        #[cfg(target_os = "windows")]
+        let path = PathBuf::from("C:\\");
+        #[cfg(not(target_os = "windows"))]
+        let path = PathBuf::from("/");
+
+        Collector {
+            metrics: Vec::new(),
+            interface: None,
+            path,
+        }
+    }
+
+    /// Adds `metric` of `readout`, which has to be read within `timeout`. \
+    /// Metrics are named after the fields of [`SystemSnapshot`], which match the names used by
+    /// [`Readouts::capabilities`](crate::Readouts::capabilities). Unknown metrics are reported
+    /// as [`ReadoutError::NotImplemented`].
+    pub fn metric<S: Into<String>>(
+        mut self,
+        readout: ReadoutKind,
+        metric: S,
+        timeout: Duration,
+    ) -> Self {
+        self.metrics.push((readout, metric.into(), timeout));
+        self
+    }
+
+    /// Sets the network interface that network metrics are queried for.
+    pub fn interface<S: Into<String>>(mut self, interface: S) -> Self {
+        self.interface = Some(interface.into());
+        self
+    }
+
+    /// Sets the path whose filesystem `disk_space` is reported for.
+    pub fn disk_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Reads every metric concurrently and waits until each of them has either finished or
+    /// missed its deadline.
+    pub fn collect(self) -> SystemSnapshot {
+        let start = Instant::now();
+        let mut snapshot = SystemSnapshot::default();
+        snapshot.network.interface = self.interface.clone();
+        let (sender, receiver) = mpsc::channel::<(usize, Apply)>();
+
+        let context = std::sync::Arc::new(Context {
+            interface: self.interface,
+            path: self.path,
+        });
+
+        // The metrics that are still being read, along with their deadline.
+        let mut pending = Vec::new();
+
+        for (index, (readout, metric, timeout)) in self.metrics.into_iter().enumerate() {
+            let Some(task) = task(readout, &metric) else {
+                continue;
+            };
+
+            let sender = sender.clone();
+            let context = context.clone();
+            let run = task.run;
+            let spawned = thread::Builder::new()
+                .name(format!("libmacchina-{metric}"))
+                .spawn(move || {
+                    // The receiver is gone if collect() has already returned.
+                    let _ = sender.send((index, run(&context)));
+                });
+
+            match spawned {
+                Ok(_) => pending.push((index, metric, start + timeout, task.fail)),
+                Err(e) => (task.fail)(&mut snapshot, ReadoutError::from(e).with_metric(metric)),
+            }
+        }
+
+        drop(sender);
+
+        while !pending.is_empty() {
+            let deadline = pending.iter().map(|(_, _, d, _)| *d).min().unwrap();
+            let now = Instant::now();
+
+            let received = if deadline > now {
+                receiver.recv_timeout(deadline - now).ok()
+            } else {
+                None
+            };
+
+            match received {
+                Some((index, apply)) => {
+                    if let Some(i) = pending.iter().position(|(p, _, _, _)| *p == index) {
+                        pending.remove(i);
+                        apply(&mut snapshot);
+                    }
+                }
+                None => {
+                    let now = Instant::now();
+                    pending.retain(|(_, metric, deadline, fail)| {
+                        if *deadline > now {
+                            return true;
+                        }
+
+                        fail(
+                            &mut snapshot,
+                            ReadoutError::failed(ReadoutErrorKind::Timeout).with_metric(metric),
+                        );
+                        false
+                    });
+                }
+            }
+        }
+
+        snapshot
+    }
+}
+
+impl Default for Collector {
+    fn default() -> Self {
+        Collector::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_with_timeouts() {
+        let snapshot = Collector::new()
+            .metric(ReadoutKind::Kernel, "os_type", Duration::from_secs(5))
+            .metric(ReadoutKind::General, "cpu_cores", Duration::ZERO)
+            .metric(
+                ReadoutKind::General,
+                "no_such_metric",
+                Duration::from_secs(5),
+            )
+            .collect();
+
+        assert!(snapshot.kernel.os_type.is_ok());
+        assert_eq!(
+            snapshot.general.cpu_cores.unwrap_err().kind(),
+            Some(ReadoutErrorKind::Timeout)
+        );
+        assert!(matches!(
+            snapshot.battery.percentage,
+            Err(ReadoutError::NotImplemented)
+        ));
+    }
+}
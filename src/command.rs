@@ -0,0 +1,188 @@
+//! This module runs the external programs some readouts depend on, e.g. `xprop` to detect the
+//! window manager or `xbps-query` to count packages.
+//!
+//! Every program is run with a timeout, and failures to start it, a non-zero exit status or a
+//! program that does not finish in time are reported as a [`ReadoutError`] rather than a panic.
+//!
+//! Callers that must never spawn processes, e.g. because they run sandboxed or setuid, can turn
+//! this off altogether. Readouts then fall back to file-based sources where they have one, and
+//! report [`ReadoutError::MetricNotAvailable`] otherwise.
+//!
+//! ```
+//! use libmacchina::command::{self, CommandPolicy};
+//!
+//! command::set_policy(CommandPolicy::Deny);
+//! assert_eq!(command::policy(), CommandPolicy::Deny);
+//! ```
+
+// Not every backend runs external programs.
+#![allow(dead_code)]
+
+use crate::traits::{ReadoutError, ReadoutErrorKind};
+use std::ffi::OsStr;
+use std::io::Read;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+static DENY: AtomicBool = AtomicBool::new(false);
+static TIMEOUT_MS: AtomicU64 = AtomicU64::new(2000);
+
+/// How often a running program is checked for having exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Whether readouts may run external programs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandPolicy {
+    /// Readouts may run external programs, which is the default.
+    Allow,
+    /// Readouts never run external programs.
+    Deny,
+}
+
+/// Sets whether readouts may run external programs. This applies to the whole process.
+pub fn set_policy(policy: CommandPolicy) {
+    DENY.store(policy == CommandPolicy::Deny, Ordering::Relaxed);
+}
+
+/// Returns whether readouts may run external programs.
+pub fn policy() -> CommandPolicy {
+    if DENY.load(Ordering::Relaxed) {
+        CommandPolicy::Deny
+    } else {
+        CommandPolicy::Allow
+    }
+}
+
+/// Sets how long an external program may run before it is killed, two seconds by default.
+pub fn set_timeout(timeout: Duration) {
+    let millis = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX);
+    TIMEOUT_MS.store(millis, Ordering::Relaxed);
+}
+
+/// Returns how long an external program may run before it is killed.
+pub fn timeout() -> Duration {
+    Duration::from_millis(TIMEOUT_MS.load(Ordering::Relaxed))
+}
+
+/// Returns `true` if readouts may run external programs.
+pub(crate) fn allowed() -> bool {
+    policy() == CommandPolicy::Allow
+}
+
+/// Runs `program` with `args` and returns what it wrote to its standard output. Output that is
+/// not valid UTF-8 is converted lossily.
+///
+/// Fails with [`ReadoutError::MetricNotAvailable`] if running programs is denied, and with a
+/// [`ReadoutErrorKind::Timeout`] failure if the program does not exit in time.
+pub(crate) fn run<I, S>(program: &str, args: I) -> Result<String, ReadoutError>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    if !allowed() {
+        return Err(ReadoutError::MetricNotAvailable);
+    }
+
+    let args: Vec<S> = args.into_iter().collect();
+    let command_line = std::iter::once(program.to_owned())
+        .chain(
+            args.iter()
+                .map(|a| a.as_ref().to_string_lossy().into_owned()),
+        )
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut child = Command::new(program)
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| ReadoutError::from(e).with_command(&command_line))?;
+
+    // Drain both pipes while waiting, so a chatty program can't block on a full pipe.
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let status = match wait(&mut child, timeout()) {
+        Ok(Some(status)) => status,
+        Ok(None) => {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(ReadoutError::failed(ReadoutErrorKind::Timeout).with_command(command_line));
+        }
+        Err(e) => {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(ReadoutError::from(e).with_command(command_line));
+        }
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    if !status.success() {
+        let stderr = String::from_utf8_lossy(&stderr);
+        let message = match stderr.trim() {
+            "" => format!("Exited with {status}"),
+            stderr => format!("Exited with {status}: {stderr}"),
+        };
+
+        return Err(ReadoutError::failed(ReadoutErrorKind::Other)
+            .with_command(command_line)
+            .with_message(message));
+    }
+
+    Ok(String::from_utf8_lossy(&stdout).into_owned())
+}
+
+/// Reads `pipe` to the end on a separate thread.
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
+
+/// Waits for `child` to exit, returning `None` if it is still running after `timeout`.
+fn wait(child: &mut Child, timeout: Duration) -> std::io::Result<Option<std::process::ExitStatus>> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+
+        if Instant::now() >= deadline {
+            return Ok(None);
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        assert_eq!(run("sh", ["-c", "printf 'a\\377b'"]).unwrap(), "a\u{FFFD}b");
+
+        let error = run("sh", ["-c", "echo oops >&2; exit 3"]).unwrap_err();
+        let failure = error.failure().unwrap();
+        assert_eq!(
+            failure.command.as_deref(),
+            Some("sh -c echo oops >&2; exit 3")
+        );
+        assert!(failure.message.as_deref().unwrap().ends_with("oops"));
+
+        let error = run("libmacchina-does-not-exist", [""; 0]).unwrap_err();
+        assert_eq!(error.kind(), Some(ReadoutErrorKind::NotFound));
+    }
+}
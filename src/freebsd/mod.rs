@@ -1,4 +1,5 @@
 #![allow(clippy::unnecessary_cast)]
+use crate::capabilities::{Capability, Prerequisite, ReadoutKind};
 use crate::extra;
 use crate::shared;
 use crate::traits::*;
@@ -9,7 +10,7 @@ use sysctl::{Ctl, Sysctl};
 
 impl From<sqlite::Error> for ReadoutError {
     fn from(e: sqlite::Error) -> Self {
-        ReadoutError::Other(e.to_string())
+        ReadoutError::from_source(ReadoutErrorKind::Other, e)
     }
 }
 
@@ -38,6 +39,7 @@ pub struct FreeBSDMemoryReadout {
 pub struct FreeBSDProductReadout;
 pub struct FreeBSDPackageReadout;
 pub struct FreeBSDNetworkReadout;
+pub struct FreeBSDSensorReadout;
 
 impl BatteryReadout for FreeBSDBatteryReadout {
     fn new() -> Self {
@@ -65,6 +67,7 @@ impl BatteryReadout for FreeBSDBatteryReadout {
                 if let Ok(to_int) = val.parse::<u8>() {
                     match to_int {
                         // https://lists.freebsd.org/pipermail/freebsd-acpi/2019-October/009753.html
+                        0 => return Ok(BatteryState::NotCharging),
                         1 => return Ok(BatteryState::Discharging),
                         2 => return Ok(BatteryState::Charging),
                         _ => {
@@ -253,6 +256,10 @@ impl GeneralReadout for FreeBSDGeneralReadout {
         shared::cpu_usage()
     }
 
+    fn load_average(&self) -> Result<LoadAverage, ReadoutError> {
+        shared::load_average()
+    }
+
     fn uptime(&self) -> Result<usize, ReadoutError> {
         let ctl = match sysctl::Ctl::new("kern.boottime") {
             Ok(ctl) => ctl,
@@ -458,3 +465,48 @@ impl NetworkReadout for FreeBSDNetworkReadout {
         Err(ReadoutError::NotImplemented)
     }
 }
+
+impl SensorReadout for FreeBSDSensorReadout {
+    fn new() -> Self {
+        FreeBSDSensorReadout
+    }
+}
+
+/// The metrics implemented by this backend.
+pub(crate) const CAPABILITIES: &[Capability] = &[
+    Capability::new(ReadoutKind::Battery, "percentage"),
+    Capability::new(ReadoutKind::Battery, "status"),
+    Capability::new(ReadoutKind::Kernel, "os_release"),
+    Capability::new(ReadoutKind::Kernel, "os_type"),
+    Capability::new(ReadoutKind::Kernel, "pretty_kernel"),
+    Capability::new(ReadoutKind::Memory, "total"),
+    Capability::new(ReadoutKind::Memory, "free"),
+    Capability::new(ReadoutKind::Memory, "used"),
+    Capability::new(ReadoutKind::General, "backlight"),
+    Capability::new(ReadoutKind::General, "resolution").requires(&[Prerequisite::X11Server]),
+    Capability::new(ReadoutKind::General, "username"),
+    Capability::new(ReadoutKind::General, "hostname"),
+    Capability::new(ReadoutKind::General, "distribution"),
+    Capability::new(ReadoutKind::General, "desktop_environment"),
+    Capability::new(ReadoutKind::General, "session"),
+    Capability::new(ReadoutKind::General, "window_manager").requires(&[
+        Prerequisite::Program("xprop"),
+        Prerequisite::Program("wmctrl"),
+    ]),
+    Capability::new(ReadoutKind::General, "terminal"),
+    Capability::new(ReadoutKind::General, "shell"),
+    Capability::new(ReadoutKind::General, "cpu_model_name"),
+    Capability::new(ReadoutKind::General, "cpu_usage"),
+    Capability::new(ReadoutKind::General, "load_average"),
+    Capability::new(ReadoutKind::General, "cpu_physical_cores"),
+    Capability::new(ReadoutKind::General, "cpu_cores"),
+    Capability::new(ReadoutKind::General, "uptime"),
+    Capability::new(ReadoutKind::General, "machine"),
+    Capability::new(ReadoutKind::General, "os_name"),
+    Capability::new(ReadoutKind::General, "disk_space"),
+    Capability::new(ReadoutKind::Product, "vendor"),
+    Capability::new(ReadoutKind::Product, "family"),
+    Capability::new(ReadoutKind::Product, "product"),
+    Capability::new(ReadoutKind::Package, "count_pkgs"),
+    Capability::new(ReadoutKind::Network, "logical_address"),
+];
@@ -12,6 +12,9 @@ cfg_if! {
         pub type ProductReadout = openwrt::OpenWrtProductReadout;
         pub type PackageReadout = openwrt::OpenWrtPackageReadout;
         pub type NetworkReadout = openwrt::OpenWrtNetworkReadout;
+        pub type SensorReadout = openwrt::OpenWrtSensorReadout;
+
+        const CAPABILITIES: &[capabilities::Capability] = openwrt::CAPABILITIES;
     } else if #[cfg(all(target_os = "linux", not(feature = "openwrt")))] {
         mod extra;
         mod linux;
@@ -24,6 +27,9 @@ cfg_if! {
         pub type ProductReadout = linux::LinuxProductReadout;
         pub type PackageReadout = linux::LinuxPackageReadout;
         pub type NetworkReadout = linux::LinuxNetworkReadout;
+        pub type SensorReadout = linux::LinuxSensorReadout;
+
+        const CAPABILITIES: &[capabilities::Capability] = linux::CAPABILITIES;
     } else if #[cfg(target_os = "macos")] {
         mod extra;
         mod macos;
@@ -35,6 +41,9 @@ cfg_if! {
         pub type ProductReadout = macos::MacOSProductReadout;
         pub type PackageReadout = macos::MacOSPackageReadout;
         pub type NetworkReadout = macos::MacOSNetworkReadout;
+        pub type SensorReadout = macos::MacOSSensorReadout;
+
+        const CAPABILITIES: &[capabilities::Capability] = macos::CAPABILITIES;
     } else if #[cfg(target_os = "netbsd")] {
         mod extra;
         mod netbsd;
@@ -48,6 +57,9 @@ cfg_if! {
         pub type ProductReadout = netbsd::NetBSDProductReadout;
         pub type PackageReadout = netbsd::NetBSDPackageReadout;
         pub type NetworkReadout = netbsd::NetBSDNetworkReadout;
+        pub type SensorReadout = netbsd::NetBSDSensorReadout;
+
+        const CAPABILITIES: &[capabilities::Capability] = netbsd::CAPABILITIES;
     } else if #[cfg(target_os = "windows")] {
         mod windows;
 
@@ -58,6 +70,9 @@ cfg_if! {
         pub type ProductReadout = windows::WindowsProductReadout;
         pub type PackageReadout = windows::WindowsPackageReadout;
         pub type NetworkReadout = windows::WindowsNetworkReadout;
+        pub type SensorReadout = windows::WindowsSensorReadout;
+
+        const CAPABILITIES: &[capabilities::Capability] = windows::CAPABILITIES;
     } else if #[cfg(target_os = "android")] {
         mod android;
         mod extra;
@@ -69,6 +84,9 @@ cfg_if! {
         pub type ProductReadout = android::AndroidProductReadout;
         pub type PackageReadout = android::AndroidPackageReadout;
         pub type NetworkReadout = android::AndroidNetworkReadout;
+        pub type SensorReadout = android::AndroidSensorReadout;
+
+        const CAPABILITIES: &[capabilities::Capability] = android::CAPABILITIES;
     } else if #[cfg(target_os = "freebsd")] {
         mod extra;
         mod freebsd;
@@ -81,6 +99,9 @@ cfg_if! {
         pub type ProductReadout = freebsd::FreeBSDProductReadout;
         pub type PackageReadout = freebsd::FreeBSDPackageReadout;
         pub type NetworkReadout = freebsd::FreeBSDNetworkReadout;
+        pub type SensorReadout = freebsd::FreeBSDSensorReadout;
+
+        const CAPABILITIES: &[capabilities::Capability] = freebsd::CAPABILITIES;
     } else {
         compiler_error!("This platform is currently not supported by libmacchina.");
     }
@@ -94,6 +115,42 @@ pub struct Readouts {
     pub product: ProductReadout,
     pub packages: PackageReadout,
     pub network: NetworkReadout,
+    pub sensors: SensorReadout,
+}
+
+impl Readouts {
+    /// Creates every readout of the current platform.
+    pub fn new() -> Self {
+        Readouts {
+            battery: traits::BatteryReadout::new(),
+            kernel: traits::KernelReadout::new(),
+            memory: traits::MemoryReadout::new(),
+            general: traits::GeneralReadout::new(),
+            product: traits::ProductReadout::new(),
+            packages: traits::PackageReadout::new(),
+            network: traits::NetworkReadout::new(),
+            sensors: traits::SensorReadout::new(),
+        }
+    }
+
+    /// Returns the metrics implemented by the backend of the current platform, along with their
+    /// runtime prerequisites.
+    pub fn capabilities() -> &'static [capabilities::Capability] {
+        CAPABILITIES
+    }
+
+    /// Returns `true` if the backend of the current platform implements `metric` of `readout`.
+    pub fn supports(readout: capabilities::ReadoutKind, metric: &str) -> bool {
+        CAPABILITIES
+            .iter()
+            .any(|c| c.readout == readout && c.metric == metric)
+    }
+}
+
+impl Default for Readouts {
+    fn default() -> Self {
+        Readouts::new()
+    }
 }
 
 #[cfg(feature = "version")]
@@ -105,5 +162,13 @@ pub fn version() -> &'static str {
     }
 }
 
+pub mod capabilities;
+pub mod collect;
+pub mod command;
+#[cfg(feature = "mock")]
+pub mod mock;
 mod shared;
+pub mod snapshot;
 pub mod traits;
+
+pub use snapshot::SystemSnapshot;
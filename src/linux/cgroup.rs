@@ -0,0 +1,251 @@
+use super::cpu::{parse_cpu_list, read_cpu_list};
+use super::read_rooted;
+use crate::shared;
+use crate::traits::{CpuLimits, LimitSource, MemoryLimits, ReadoutError};
+use std::path::{Path, PathBuf};
+
+/// The version of the cgroup hierarchy a controller is mounted in, which decides the names of
+/// its files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Version {
+    V1,
+    V2,
+}
+
+/// Returns the directory of the cgroup the calling process belongs to for `controller`,
+/// followed by those of its ancestors up to the root of the hierarchy.
+///
+/// Inside a container without a cgroup namespace, `proc/self/cgroup` lists the path on the
+/// host, which isn't mounted in the container, and the root of the hierarchy is used instead.
+fn cgroup_dirs(root: &Path, controller: &str) -> Option<(Version, Vec<PathBuf>)> {
+    let content = read_rooted(root, "proc/self/cgroup").ok()?;
+    let base = root.join("sys/fs/cgroup");
+
+    // _e.g._ `4:cpu,cpuacct:/docker/0123` for v1 and `0::/user.slice` for v2
+    let entries: Vec<(&str, &str)> = content
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once(':')?;
+            rest.split_once(':')
+        })
+        .collect();
+
+    let v1 = entries.iter().find_map(|(controllers, path)| {
+        if !controllers.split(',').any(|c| c == controller) {
+            return None;
+        }
+
+        [base.join(controllers), base.join(controller)]
+            .into_iter()
+            .find(|mount| mount.is_dir())
+            .map(|mount| (Version::V1, mount, *path))
+    });
+
+    let v2 = || {
+        let (_, path) = entries
+            .iter()
+            .find(|(controllers, _)| controllers.is_empty())?;
+        base.join("cgroup.controllers")
+            .exists()
+            .then(|| (Version::V2, base.clone(), *path))
+    };
+
+    let (version, mount, path) = v1.or_else(v2)?;
+    let leaf = match mount.join(path.trim_start_matches('/')) {
+        leaf if leaf.is_dir() => leaf,
+        _ => mount.clone(),
+    };
+
+    let dirs = leaf
+        .ancestors()
+        .take_while(|dir| dir.starts_with(&mount))
+        .map(Path::to_path_buf)
+        .collect();
+
+    Some((version, dirs))
+}
+
+/// Reads a limit from `name` in `dir`, where `max` and, in cgroup v1, `-1` or values close to
+/// `i64::MAX` mean that there is none.
+fn read_limit(dir: &Path, name: &str) -> Option<u64> {
+    let value = read_rooted(dir, name).ok()?;
+    let limit: u64 = value.split_whitespace().next()?.parse().ok()?;
+
+    (limit < 1 << 62).then_some(limit)
+}
+
+/// Returns the lowest limit in `name` among `dirs`.
+fn lowest_limit(dirs: &[PathBuf], name: &str) -> Option<u64> {
+    dirs.iter().filter_map(|dir| read_limit(dir, name)).min()
+}
+
+/// Reads the memory limit of the cgroup of the calling process, falling back to the memory of
+/// the host if there is none or it exceeds the memory of the host.
+pub(super) fn memory_limits(root: &Path) -> Result<MemoryLimits, ReadoutError> {
+    let host = shared::read_meminfo_at(root)?;
+    let host_limits = MemoryLimits {
+        limit: host.total,
+        usage: host.used(),
+        swap_limit: Some(host.swap_total),
+        source: LimitSource::Host,
+    };
+
+    let Some((version, dirs)) = cgroup_dirs(root, "memory") else {
+        return Ok(host_limits);
+    };
+
+    let (limit, usage, swap_limit) = match version {
+        Version::V2 => (
+            lowest_limit(&dirs, "memory.max"),
+            read_limit(&dirs[0], "memory.current"),
+            lowest_limit(&dirs, "memory.swap.max"),
+        ),
+        Version::V1 => {
+            let limit = lowest_limit(&dirs, "memory.limit_in_bytes");
+            // v1 limits memory and swap combined.
+            let combined = lowest_limit(&dirs, "memory.memsw.limit_in_bytes");
+            (
+                limit,
+                read_limit(&dirs[0], "memory.usage_in_bytes"),
+                combined.zip(limit).map(|(c, l)| c.saturating_sub(l)),
+            )
+        }
+    };
+
+    match (limit.map(|bytes| bytes / 1024), usage) {
+        (Some(limit), Some(usage)) if limit < host.total => Ok(MemoryLimits {
+            limit,
+            usage: usage / 1024,
+            swap_limit: swap_limit.map(|bytes| bytes / 1024),
+            source: LimitSource::Cgroup,
+        }),
+        _ => Ok(host_limits),
+    }
+}
+
+/// Reads the CPU quota and the CPUs the cgroup of the calling process may run on, falling back
+/// to the online CPUs of the host.
+pub(super) fn cpu_limits(root: &Path) -> Result<CpuLimits, ReadoutError> {
+    let online = read_cpu_list(root, "sys/devices/system/cpu/online").unwrap_or_else(|| {
+        (0..unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) } as usize).collect()
+    });
+
+    let quota = cgroup_dirs(root, "cpu").and_then(|(version, dirs)| {
+        dirs.iter()
+            .filter_map(|dir| match version {
+                // _e.g._ `150000 100000`, or `max 100000` without a quota
+                Version::V2 => {
+                    let value = read_rooted(dir, "cpu.max").ok()?;
+                    let (quota, period) = value.split_once(' ')?;
+                    Some(quota.parse::<f64>().ok()? / period.parse::<f64>().ok()?)
+                }
+                Version::V1 => {
+                    let quota = read_limit(dir, "cpu.cfs_quota_us")?;
+                    let period = read_limit(dir, "cpu.cfs_period_us")?;
+                    Some(quota as f64 / period as f64)
+                }
+            })
+            .reduce(f64::min)
+            .filter(|quota| *quota < online.len() as f64)
+    });
+
+    let cpus = cgroup_dirs(root, "cpuset")
+        .and_then(|(version, dirs)| {
+            let name = match version {
+                Version::V2 => "cpuset.cpus.effective",
+                Version::V1 => "cpuset.effective_cpus",
+            };
+            parse_cpu_list(&read_rooted(&dirs[0], name).ok()?)
+        })
+        .filter(|cpus| !cpus.is_empty() && cpus.len() < online.len());
+
+    if quota.is_none() && cpus.is_none() {
+        return Ok(CpuLimits {
+            cores: online.len() as f64,
+            cpus: online,
+            source: LimitSource::Host,
+        });
+    }
+
+    let cpus = cpus.unwrap_or(online);
+    let cores = quota.map_or(cpus.len() as f64, |quota| quota.min(cpus.len() as f64));
+
+    Ok(CpuLimits {
+        cores,
+        cpus,
+        source: LimitSource::Cgroup,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::linux::fixture::Fixture;
+    use crate::linux::{LinuxGeneralReadout, LinuxMemoryReadout};
+    use crate::traits::*;
+    use std::fs;
+
+    #[test]
+    fn test_cgroup_limits() {
+        let meminfo = "MemTotal: 16318460 kB\nMemFree: 1034880 kB\nMemAvailable: 9871204 kB\n\
+                       Buffers: 412560 kB\nCached: 8402136 kB\n\
+                       SwapTotal: 8388604 kB\nSwapFree: 8388604 kB\n";
+        let root = Fixture::new(
+            "cgroup",
+            &[
+                ("proc/meminfo", meminfo),
+                ("proc/self/cgroup", "0::/kubepods/pod1\n"),
+                ("sys/devices/system/cpu/online", "0-7\n"),
+                ("sys/fs/cgroup/cgroup.controllers", "cpuset cpu io memory\n"),
+                ("sys/fs/cgroup/kubepods/memory.max", "1073741824\n"),
+                ("sys/fs/cgroup/kubepods/pod1/memory.max", "max\n"),
+                ("sys/fs/cgroup/kubepods/pod1/memory.current", "268435456\n"),
+                ("sys/fs/cgroup/kubepods/pod1/memory.swap.max", "0\n"),
+                ("sys/fs/cgroup/kubepods/pod1/cpu.max", "150000 100000\n"),
+                ("sys/fs/cgroup/kubepods/pod1/cpuset.cpus.effective", "0-3\n"),
+            ],
+        );
+
+        let memory = LinuxMemoryReadout::with_root(root.path());
+        let general = LinuxGeneralReadout::with_root(root.path());
+        assert_eq!(
+            memory.limits().unwrap(),
+            MemoryLimits {
+                limit: 1048576,
+                usage: 262144,
+                swap_limit: Some(0),
+                source: LimitSource::Cgroup,
+            }
+        );
+        assert_eq!(
+            general.cpu_limits().unwrap(),
+            CpuLimits {
+                cores: 1.5,
+                cpus: vec![0, 1, 2, 3],
+                source: LimitSource::Cgroup,
+            }
+        );
+
+        // A cgroup v1 container without limits, whose path on the host isn't mounted.
+        fs::remove_dir_all(root.path().join("sys/fs/cgroup")).unwrap();
+        fs::write(
+            root.path().join("proc/self/cgroup"),
+            "5:memory:/docker/0123\n4:cpu,cpuacct:/docker/0123\n0::/\n",
+        )
+        .unwrap();
+        for (path, contents) in [
+            ("memory/memory.limit_in_bytes", "9223372036854771712\n"),
+            ("memory/memory.usage_in_bytes", "268435456\n"),
+            ("cpu,cpuacct/cpu.cfs_quota_us", "-1\n"),
+            ("cpu,cpuacct/cpu.cfs_period_us", "100000\n"),
+        ] {
+            let path = root.path().join("sys/fs/cgroup").join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        let limits = memory.limits().unwrap();
+        assert_eq!((limits.limit, limits.source), (16318460, LimitSource::Host));
+        let limits = general.cpu_limits().unwrap();
+        assert_eq!((limits.cores, limits.source), (8.0, LimitSource::Host));
+    }
+}
@@ -0,0 +1,895 @@
+use super::{numbered_entries, parse_file, read_rooted};
+use crate::extra::get_entries;
+use crate::shared;
+use crate::traits::{
+    CacheKind, CoreFrequency, CoreType, CpuCache, CpuCluster, CpuFeatures, CpuFrequency,
+    CpuTopology, CpuVulnerability, ReadoutError, VulnerabilityStatus,
+};
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Returns the directory of every logical CPU under `sys/devices/system/cpu`, along with its
+/// number.
+fn cpu_dirs(root: &Path) -> Vec<(usize, PathBuf)> {
+    numbered_entries(&root.join("sys/devices/system/cpu"), "cpu")
+        .into_iter()
+        .filter_map(|dir| {
+            let cpu = dir
+                .file_name()?
+                .to_str()?
+                .strip_prefix("cpu")?
+                .parse()
+                .ok()?;
+            Some((cpu, dir))
+        })
+        .collect()
+}
+
+/// Appends `item` to `items` unless it is already there.
+fn insert<T: PartialEq>(items: &mut Vec<T>, item: T) {
+    if !items.contains(&item) {
+        items.push(item);
+    }
+}
+
+/// Reads a frequency in kHz from `dir`, converted to MHz.
+fn read_khz(dir: &Path, name: &str) -> Option<f64> {
+    parse_file::<f64>(&dir.join(name))
+        .ok()
+        .map(|khz| khz / 1000.0)
+}
+
+/// Reads the frequency of every CPU from cpufreq, with the driver and policy of the first CPU
+/// that reports them. Returns `None` if cpufreq isn't available, _e.g._ in most VMs.
+fn read_cpufreq(root: &Path) -> Option<CpuFrequency> {
+    let cpus = root.join("sys/devices/system/cpu");
+    let mut frequency = CpuFrequency::default();
+
+    for (cpu, dir) in cpu_dirs(root) {
+        let cpufreq = dir.join("cpufreq");
+        if !cpufreq.is_dir() {
+            continue;
+        }
+
+        let setting = |name: &str| read_rooted(&cpufreq, name).ok();
+        frequency.driver = frequency.driver.or_else(|| setting("scaling_driver"));
+        frequency.governor = frequency.governor.or_else(|| setting("scaling_governor"));
+        frequency.energy_performance_preference = frequency
+            .energy_performance_preference
+            .or_else(|| setting("energy_performance_preference"));
+
+        frequency.cores.push(CoreFrequency {
+            cpu,
+            current: read_khz(&cpufreq, "scaling_cur_freq")
+                .or_else(|| read_khz(&cpufreq, "cpuinfo_cur_freq")),
+            min: read_khz(&cpufreq, "cpuinfo_min_freq")
+                .or_else(|| read_khz(&cpufreq, "scaling_min_freq")),
+            max: read_khz(&cpufreq, "cpuinfo_max_freq")
+                .or_else(|| read_khz(&cpufreq, "scaling_max_freq")),
+        });
+    }
+
+    if frequency.cores.is_empty() {
+        return None;
+    }
+
+    frequency.boost = read_boost(&cpus);
+    Some(frequency)
+}
+
+/// Reads whether boost is enabled. `acpi-cpufreq` and `amd-pstate` expose a global `boost`
+/// switch, newer kernels one per policy, and `intel_pstate` its inverse, `no_turbo`.
+fn read_boost(cpus: &Path) -> Option<bool> {
+    let flag = |path: &str| parse_file::<u8>(&cpus.join(path)).ok().map(|v| v != 0);
+
+    flag("cpufreq/boost")
+        .or_else(|| flag("intel_pstate/no_turbo").map(|no_turbo| !no_turbo))
+        .or_else(|| flag("cpufreq/policy0/boost"))
+}
+
+/// Reads the current frequency of every CPU from the `cpu MHz` lines of `proc/cpuinfo`.
+fn read_cpuinfo_frequency(root: &Path) -> Result<CpuFrequency, ReadoutError> {
+    let cores: Vec<CoreFrequency> = shared::read_cpuinfo(root)?
+        .iter()
+        .filter_map(|block| {
+            Some(CoreFrequency {
+                cpu: block.processor()?,
+                current: Some(block.get("cpu MHz")?.parse().ok()?),
+                ..Default::default()
+            })
+        })
+        .collect();
+
+    if cores.is_empty() {
+        return Err(ReadoutError::MetricNotAvailable);
+    }
+
+    Ok(CpuFrequency {
+        cores,
+        ..Default::default()
+    })
+}
+
+/// Reads the frequency of every CPU from cpufreq, falling back to `proc/cpuinfo`.
+pub(super) fn cpu_frequency(root: &Path) -> Result<CpuFrequency, ReadoutError> {
+    match read_cpufreq(root) {
+        Some(frequency) => Ok(frequency),
+        None => read_cpuinfo_frequency(root),
+    }
+}
+
+/// Parses a list of CPUs in the kernel's format, _e.g._ `0-3,8-11,16`. Returns `None` if the
+/// list is malformed or empty.
+pub(super) fn parse_cpu_list(list: &str) -> Option<Vec<usize>> {
+    let mut cpus = Vec::new();
+
+    for range in list.trim().split(',').filter(|range| !range.is_empty()) {
+        match range.split_once('-') {
+            Some((start, end)) => cpus.extend(start.parse::<usize>().ok()?..=end.parse().ok()?),
+            None => cpus.push(range.parse().ok()?),
+        }
+    }
+
+    (!cpus.is_empty()).then_some(cpus)
+}
+
+/// Reads the CPU list in `path`, _e.g._ `sys/devices/system/cpu/online`.
+pub(super) fn read_cpu_list(root: &Path, path: &str) -> Option<Vec<usize>> {
+    parse_cpu_list(&read_rooted(root, path).ok()?)
+}
+
+/// Parses a cache size such as `32K` or `8M` into bytes.
+fn parse_cache_size(size: &str) -> Option<u64> {
+    let size = size.trim();
+    let (number, multiplier) = match size.char_indices().last()? {
+        (i, 'K') => (&size[..i], 1 << 10),
+        (i, 'M') => (&size[..i], 1 << 20),
+        (i, 'G') => (&size[..i], 1 << 30),
+        _ => (size, 1),
+    };
+
+    Some(number.parse::<u64>().ok()? * multiplier)
+}
+
+/// Reads the caches of every online CPU. Each instance of a cache is reported by every CPU
+/// sharing it, so instances are told apart by the CPUs they are shared with.
+fn read_caches(cpus: &[(usize, PathBuf)]) -> Vec<CpuCache> {
+    let mut instances: Vec<(u8, CacheKind, u64, String)> = Vec::new();
+
+    for (_, dir) in cpus {
+        for index in numbered_entries(&dir.join("cache"), "index") {
+            let kind = match read_rooted(&index, "type").as_deref() {
+                Ok("Data") => CacheKind::Data,
+                Ok("Instruction") => CacheKind::Instruction,
+                Ok("Unified") => CacheKind::Unified,
+                _ => continue,
+            };
+
+            let (Ok(level), Some(size), Ok(shared)) = (
+                parse_file::<u8>(&index.join("level")),
+                read_rooted(&index, "size")
+                    .ok()
+                    .and_then(|size| parse_cache_size(&size)),
+                read_rooted(&index, "shared_cpu_list"),
+            ) else {
+                continue;
+            };
+
+            insert(&mut instances, (level, kind, size, shared));
+        }
+    }
+
+    let mut caches: Vec<CpuCache> = Vec::new();
+    for (level, kind, size, shared) in instances {
+        let shared_by = parse_cpu_list(&shared).map_or(1, |cpus| cpus.len());
+
+        match caches
+            .iter_mut()
+            .find(|c| c.level == level && c.kind == kind && c.size == size)
+        {
+            Some(cache) => cache.instances += 1,
+            None => caches.push(CpuCache {
+                level,
+                kind,
+                size,
+                instances: 1,
+                shared_by,
+            }),
+        }
+    }
+
+    caches.sort_by_key(|cache| (cache.level, cache.kind as u8));
+    caches
+}
+
+/// The relative capacity, highest frequency and part number of an ARM core.
+type ClusterKey<'a> = (Option<u32>, Option<u64>, Option<&'a str>);
+
+/// Groups the online CPUs of a hybrid CPU into clusters of identical cores. Intel lists its
+/// P-cores and E-cores as separate PMUs, while ARM cores are told apart by their relative
+/// capacity, highest frequency and part number, provided they report a capacity or part number.
+fn read_clusters<C: PartialEq>(
+    root: &Path,
+    cpus: &[(usize, PathBuf)],
+    core_of: &[(usize, C)],
+) -> Vec<CpuCluster> {
+    let mut groups: Vec<(CoreType, Vec<usize>)> = Vec::new();
+    let cpuinfo = shared::read_cpuinfo(root).unwrap_or_default();
+    let block = |cpu: usize| cpuinfo.iter().find(|block| block.processor() == Some(cpu));
+
+    if let (Some(performance), Some(efficiency)) = (
+        read_cpu_list(root, "sys/devices/cpu_core/cpus"),
+        read_cpu_list(root, "sys/devices/cpu_atom/cpus"),
+    ) {
+        // The PMUs also list CPUs that are offline.
+        let online = |list: Vec<usize>| -> Vec<usize> {
+            list.into_iter()
+                .filter(|cpu| cpus.iter().any(|(c, _)| c == cpu))
+                .collect()
+        };
+
+        groups.push((CoreType::Performance, online(performance)));
+        groups.push((CoreType::Efficiency, online(efficiency)));
+    } else {
+        let mut kinds: Vec<(ClusterKey, Vec<usize>)> = Vec::new();
+
+        for (cpu, dir) in cpus {
+            let key = (
+                parse_file::<u32>(&dir.join("cpu_capacity")).ok(),
+                parse_file::<u64>(&dir.join("cpufreq/cpuinfo_max_freq")).ok(),
+                block(*cpu).and_then(|block| block.get("CPU part")),
+            );
+
+            match kinds.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, members)) => members.push(*cpu),
+                None => kinds.push((key, vec![*cpu])),
+            }
+        }
+
+        // x86 reports neither a capacity nor a part number, and its preferred cores merely
+        // boost higher than the others.
+        let heterogeneous = kinds
+            .iter()
+            .any(|((capacity, _, part), _)| capacity.is_some() || part.is_some());
+
+        if kinds.len() < 2 || !heterogeneous {
+            return Vec::new();
+        }
+
+        // Only the slowest cluster is considered efficient, SoCs with a third "prime"
+        // cluster have two clusters of performance cores.
+        kinds.sort_by_key(|((capacity, frequency, _), _)| Reverse((*capacity, *frequency)));
+        let slowest = kinds.len() - 1;
+
+        for (i, (_, members)) in kinds.into_iter().enumerate() {
+            let core_type = if i == slowest {
+                CoreType::Efficiency
+            } else {
+                CoreType::Performance
+            };
+            groups.push((core_type, members));
+        }
+    }
+
+    // With all of its E-cores offline, a hybrid CPU is left with a single kind of core.
+    groups.retain(|(_, members)| !members.is_empty());
+    if groups.len() < 2 {
+        return Vec::new();
+    }
+
+    groups
+        .into_iter()
+        .map(|(core_type, members)| {
+            let mut cores = Vec::new();
+            for (_, core) in core_of.iter().filter(|(cpu, _)| members.contains(cpu)) {
+                insert(&mut cores, core);
+            }
+
+            let dirs = || {
+                cpus.iter()
+                    .filter(|(cpu, _)| members.contains(cpu))
+                    .map(|(_, dir)| dir.join("cpufreq"))
+            };
+
+            CpuCluster {
+                core_type,
+                cores: cores.len(),
+                model: members.first().and_then(|cpu| block(*cpu)?.model_name()),
+                min_frequency: dirs()
+                    .filter_map(|dir| read_khz(&dir, "cpuinfo_min_freq"))
+                    .reduce(f64::min),
+                max_frequency: dirs()
+                    .filter_map(|dir| read_khz(&dir, "cpuinfo_max_freq"))
+                    .reduce(f64::max),
+                cpus: members,
+            }
+        })
+        .collect()
+}
+
+/// Reads the topology of the online CPUs from `sys/devices/system/cpu`.
+pub(super) fn cpu_topology(root: &Path) -> Result<CpuTopology, ReadoutError> {
+    let online = read_cpu_list(root, "sys/devices/system/cpu/online");
+    let possible = read_cpu_list(root, "sys/devices/system/cpu/possible");
+
+    // Offline CPUs don't report their topology.
+    let cpus: Vec<(usize, PathBuf)> = cpu_dirs(root)
+        .into_iter()
+        .filter(|(cpu, dir)| match &online {
+            Some(online) => online.contains(cpu),
+            None => dir.join("topology").is_dir(),
+        })
+        .collect();
+
+    let mut packages = Vec::new();
+    let mut dies = Vec::new();
+    let mut cores = Vec::new();
+    let mut core_of = Vec::new();
+
+    for (cpu, dir) in &cpus {
+        let topology = dir.join("topology");
+        let id = |name: &str| parse_file::<i64>(&topology.join(name)).ok();
+
+        let Some(package) = id("physical_package_id") else {
+            continue;
+        };
+
+        // Kernels before 5.5 don't report dies, and treat each package as a single die.
+        let die = (package, id("die_id").unwrap_or(0));
+        let core = (die, id("core_id").unwrap_or_default());
+
+        insert(&mut packages, package);
+        insert(&mut dies, die);
+        insert(&mut cores, core);
+        core_of.push((*cpu, core));
+    }
+
+    if cores.is_empty() {
+        return Err(ReadoutError::MetricNotAvailable);
+    }
+
+    let threads = online.map_or(cpus.len(), |online| online.len());
+
+    Ok(CpuTopology {
+        sockets: packages.len(),
+        dies: dies.len(),
+        cores: cores.len(),
+        threads,
+        possible: possible.map_or(threads, |possible| possible.len()),
+        numa_nodes: numbered_entries(&root.join("sys/devices/system/node"), "node").len(),
+        caches: read_caches(&cpus),
+        clusters: read_clusters(root, &cpus, &core_of),
+    })
+}
+
+/// Splits a RISC-V ISA string such as `rv64imafdc_zicsr_zifencei` into its extensions.
+fn parse_riscv_isa(isa: &str) -> BTreeSet<String> {
+    let isa = isa.to_lowercase();
+    let mut extensions = isa.split('_');
+    let base = extensions.next().unwrap_or_default();
+    let base = base
+        .strip_prefix("rv64")
+        .or_else(|| base.strip_prefix("rv32"))
+        .unwrap_or(base);
+
+    base.chars()
+        .map(String::from)
+        .chain(extensions.filter(|ext| !ext.is_empty()).map(str::to_owned))
+        .collect()
+}
+
+/// Reads the extensions every CPU in `proc/cpuinfo` supports, from `flags` on x86, `Features`
+/// on ARM and `isa` on RISC-V.
+pub(super) fn cpu_features(root: &Path) -> Result<CpuFeatures, ReadoutError> {
+    let mut common: Option<BTreeSet<String>> = None;
+
+    for block in shared::read_cpuinfo(root)? {
+        let flags = if let Some(isa) = block.get("isa") {
+            parse_riscv_isa(isa)
+        } else if let Some(flags) = block.get("flags").or_else(|| block.get("Features")) {
+            flags.split_whitespace().map(str::to_owned).collect()
+        } else {
+            continue;
+        };
+
+        // CPUs of hybrid processors may differ, only the extensions all of them share count.
+        match &mut common {
+            Some(common) => common.retain(|flag| flags.contains(flag)),
+            None => common = Some(flags),
+        }
+    }
+
+    common
+        .map(|flags| CpuFeatures { flags })
+        .ok_or(ReadoutError::MetricNotAvailable)
+}
+
+/// Parses the contents of a file in `sys/devices/system/cpu/vulnerabilities`.
+fn parse_vulnerability(status: &str) -> VulnerabilityStatus {
+    // itlb_multihit describes the mitigation in KVM, _e.g._ `KVM: Mitigation: VMX disabled`.
+    let status = status.strip_prefix("KVM: ").unwrap_or(status);
+
+    if status == "Not affected" {
+        VulnerabilityStatus::NotAffected
+    } else if let Some(mitigation) = status.strip_prefix("Mitigation: ") {
+        VulnerabilityStatus::Mitigated(mitigation.to_owned())
+    } else if let Some(details) = status.strip_prefix("Vulnerable") {
+        let details = details.trim_start_matches([':', ';', ',', ' ']);
+        VulnerabilityStatus::Vulnerable(Some(details.to_owned()).filter(|d| !d.is_empty()))
+    } else {
+        let details = status.strip_prefix("Unknown: ").unwrap_or(status);
+        VulnerabilityStatus::Unknown(details.to_owned())
+    }
+}
+
+/// Reads the status of every vulnerability in `sys/devices/system/cpu/vulnerabilities`.
+pub(super) fn cpu_vulnerabilities(root: &Path) -> Result<Vec<CpuVulnerability>, ReadoutError> {
+    let dir = root.join("sys/devices/system/cpu/vulnerabilities");
+    let mut entries = get_entries(&dir).ok_or(ReadoutError::MetricNotAvailable)?;
+    entries.sort();
+
+    Ok(entries
+        .iter()
+        .filter_map(|entry| {
+            Some(CpuVulnerability {
+                name: entry.file_name()?.to_str()?.to_owned(),
+                status: parse_vulnerability(shared::read_file(entry).ok()?.trim()),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::linux::fixture::Fixture;
+    use crate::linux::LinuxGeneralReadout;
+    use crate::traits::*;
+    use std::fs;
+
+    #[test]
+    fn test_cpu_frequency() {
+        let root = Fixture::new(
+            "cpufreq",
+            &[
+                (
+                    "sys/devices/system/cpu/cpu0/cpufreq/scaling_cur_freq",
+                    "4850000\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu0/cpufreq/cpuinfo_min_freq",
+                    "2200000\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu0/cpufreq/cpuinfo_max_freq",
+                    "4850000\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu0/cpufreq/scaling_driver",
+                    "amd-pstate-epp\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu0/cpufreq/scaling_governor",
+                    "powersave\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu0/cpufreq/energy_performance_preference",
+                    "balance_performance\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu1/cpufreq/scaling_cur_freq",
+                    "2200000\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu1/cpufreq/cpuinfo_max_freq",
+                    "4600000\n",
+                ),
+                ("sys/devices/system/cpu/cpufreq/boost", "1\n"),
+                ("proc/cpuinfo", "processor\t: 0\ncpu MHz\t\t: 3000.000\n"),
+            ],
+        );
+
+        let frequency = LinuxGeneralReadout::with_root(root.path())
+            .cpu_frequency()
+            .unwrap();
+        assert_eq!(frequency.cores.len(), 2);
+        assert_eq!(
+            frequency.cores[1],
+            CoreFrequency {
+                cpu: 1,
+                current: Some(2200.0),
+                min: None,
+                max: Some(4600.0),
+            }
+        );
+        assert_eq!(frequency.driver.as_deref(), Some("amd-pstate-epp"));
+        assert_eq!(frequency.governor.as_deref(), Some("powersave"));
+        assert_eq!(
+            frequency.energy_performance_preference.as_deref(),
+            Some("balance_performance")
+        );
+        assert_eq!(frequency.boost, Some(true));
+        assert_eq!(frequency.max(), Some(4850.0));
+
+        // Without cpufreq, e.g. in a VM, only the current frequency is known.
+        fs::remove_dir_all(root.path().join("sys")).unwrap();
+        let frequency = LinuxGeneralReadout::with_root(root.path())
+            .cpu_frequency()
+            .unwrap();
+        assert_eq!(frequency.cores[0].current, Some(3000.0));
+        assert_eq!((frequency.driver.as_deref(), frequency.boost), (None, None));
+        assert_eq!(frequency.max(), Some(3000.0));
+    }
+
+    #[test]
+    fn test_cpu_topology() {
+        // Two sockets with a single core and two threads each, and a hot-pluggable CPU.
+        let root = Fixture::new(
+            "topology",
+            &[
+                (
+                    "sys/devices/system/cpu/cpu0/topology/physical_package_id",
+                    "0\n",
+                ),
+                ("sys/devices/system/cpu/cpu0/topology/die_id", "0\n"),
+                ("sys/devices/system/cpu/cpu0/topology/core_id", "0\n"),
+                ("sys/devices/system/cpu/cpu0/cache/index0/level", "1\n"),
+                ("sys/devices/system/cpu/cpu0/cache/index0/type", "Data\n"),
+                ("sys/devices/system/cpu/cpu0/cache/index0/size", "32K\n"),
+                (
+                    "sys/devices/system/cpu/cpu0/cache/index0/shared_cpu_list",
+                    "0-1\n",
+                ),
+                ("sys/devices/system/cpu/cpu0/cache/index3/level", "3\n"),
+                ("sys/devices/system/cpu/cpu0/cache/index3/type", "Unified\n"),
+                ("sys/devices/system/cpu/cpu0/cache/index3/size", "32768K\n"),
+                (
+                    "sys/devices/system/cpu/cpu0/cache/index3/shared_cpu_list",
+                    "0-1\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu1/topology/physical_package_id",
+                    "0\n",
+                ),
+                ("sys/devices/system/cpu/cpu1/topology/die_id", "0\n"),
+                ("sys/devices/system/cpu/cpu1/topology/core_id", "0\n"),
+                ("sys/devices/system/cpu/cpu1/cache/index0/level", "1\n"),
+                ("sys/devices/system/cpu/cpu1/cache/index0/type", "Data\n"),
+                ("sys/devices/system/cpu/cpu1/cache/index0/size", "32K\n"),
+                (
+                    "sys/devices/system/cpu/cpu1/cache/index0/shared_cpu_list",
+                    "0-1\n",
+                ),
+                ("sys/devices/system/cpu/cpu1/cache/index3/level", "3\n"),
+                ("sys/devices/system/cpu/cpu1/cache/index3/type", "Unified\n"),
+                ("sys/devices/system/cpu/cpu1/cache/index3/size", "32768K\n"),
+                (
+                    "sys/devices/system/cpu/cpu1/cache/index3/shared_cpu_list",
+                    "0-1\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu2/topology/physical_package_id",
+                    "1\n",
+                ),
+                ("sys/devices/system/cpu/cpu2/topology/die_id", "0\n"),
+                ("sys/devices/system/cpu/cpu2/topology/core_id", "0\n"),
+                ("sys/devices/system/cpu/cpu2/cache/index0/level", "1\n"),
+                ("sys/devices/system/cpu/cpu2/cache/index0/type", "Data\n"),
+                ("sys/devices/system/cpu/cpu2/cache/index0/size", "32K\n"),
+                (
+                    "sys/devices/system/cpu/cpu2/cache/index0/shared_cpu_list",
+                    "2-3\n",
+                ),
+                ("sys/devices/system/cpu/cpu2/cache/index3/level", "3\n"),
+                ("sys/devices/system/cpu/cpu2/cache/index3/type", "Unified\n"),
+                ("sys/devices/system/cpu/cpu2/cache/index3/size", "32768K\n"),
+                (
+                    "sys/devices/system/cpu/cpu2/cache/index3/shared_cpu_list",
+                    "2-3\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu3/topology/physical_package_id",
+                    "1\n",
+                ),
+                ("sys/devices/system/cpu/cpu3/topology/die_id", "0\n"),
+                ("sys/devices/system/cpu/cpu3/topology/core_id", "0\n"),
+                ("sys/devices/system/cpu/cpu3/cache/index0/level", "1\n"),
+                ("sys/devices/system/cpu/cpu3/cache/index0/type", "Data\n"),
+                ("sys/devices/system/cpu/cpu3/cache/index0/size", "32K\n"),
+                (
+                    "sys/devices/system/cpu/cpu3/cache/index0/shared_cpu_list",
+                    "2-3\n",
+                ),
+                ("sys/devices/system/cpu/cpu3/cache/index3/level", "3\n"),
+                ("sys/devices/system/cpu/cpu3/cache/index3/type", "Unified\n"),
+                ("sys/devices/system/cpu/cpu3/cache/index3/size", "32768K\n"),
+                (
+                    "sys/devices/system/cpu/cpu3/cache/index3/shared_cpu_list",
+                    "2-3\n",
+                ),
+                ("sys/devices/system/cpu/cpu4/online", "0\n"),
+                ("sys/devices/system/cpu/online", "0-3\n"),
+                ("sys/devices/system/cpu/possible", "0-4\n"),
+                ("sys/devices/system/node/node0/cpulist", "0-1\n"),
+                ("sys/devices/system/node/node1/cpulist", "2-3\n"),
+                ("proc/cpuinfo", ""),
+            ],
+        );
+
+        let readout = LinuxGeneralReadout::with_root(root.path());
+        let topology = readout.cpu_topology().unwrap();
+        assert_eq!((topology.sockets, topology.dies, topology.cores), (2, 2, 2));
+        assert_eq!((topology.threads, topology.possible), (4, 5));
+        assert_eq!(topology.numa_nodes, 2);
+        assert_eq!(
+            topology.caches,
+            [
+                CpuCache {
+                    level: 1,
+                    kind: CacheKind::Data,
+                    size: 32 << 10,
+                    instances: 2,
+                    shared_by: 2,
+                },
+                CpuCache {
+                    level: 3,
+                    kind: CacheKind::Unified,
+                    size: 32 << 20,
+                    instances: 2,
+                    shared_by: 2,
+                },
+            ]
+        );
+
+        assert_eq!(readout.cpu_physical_cores().unwrap(), 2);
+        assert_eq!(readout.cpu_cores().unwrap(), 4);
+
+        // An empty list of online CPUs falls back to the CPUs the kernel reports.
+        fs::write(root.path().join("sys/devices/system/cpu/online"), "\n").unwrap();
+        assert_ne!(readout.cpu_cores().unwrap(), 0);
+
+        // Without sysfs, the cores of each socket listed in cpuinfo are summed.
+        fs::remove_dir_all(root.path().join("sys")).unwrap();
+        fs::write(
+            root.path().join("proc/cpuinfo"),
+            "physical id\t: 0\ncpu cores\t: 8\n\nphysical id\t: 0\ncpu cores\t: 8\n\n\
+             physical id\t: 1\ncpu cores\t: 8\n",
+        )
+        .unwrap();
+        assert_eq!(readout.cpu_physical_cores().unwrap(), 16);
+    }
+
+    #[test]
+    fn test_cpu_clusters() {
+        // An ARM SoC with two Cortex-A55 and two Cortex-A76 cores.
+        let root = Fixture::new(
+            "clusters",
+            &[
+                (
+                    "sys/devices/system/cpu/cpu0/topology/physical_package_id",
+                    "0\n",
+                ),
+                ("sys/devices/system/cpu/cpu0/topology/core_id", "0\n"),
+                ("sys/devices/system/cpu/cpu0/cpu_capacity", "446\n"),
+                (
+                    "sys/devices/system/cpu/cpu0/cpufreq/cpuinfo_min_freq",
+                    "408000\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu0/cpufreq/cpuinfo_max_freq",
+                    "1800000\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu1/topology/physical_package_id",
+                    "0\n",
+                ),
+                ("sys/devices/system/cpu/cpu1/topology/core_id", "1\n"),
+                ("sys/devices/system/cpu/cpu1/cpu_capacity", "446\n"),
+                (
+                    "sys/devices/system/cpu/cpu1/cpufreq/cpuinfo_min_freq",
+                    "408000\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu1/cpufreq/cpuinfo_max_freq",
+                    "1800000\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu2/topology/physical_package_id",
+                    "0\n",
+                ),
+                ("sys/devices/system/cpu/cpu2/topology/core_id", "2\n"),
+                ("sys/devices/system/cpu/cpu2/cpu_capacity", "1024\n"),
+                (
+                    "sys/devices/system/cpu/cpu2/cpufreq/cpuinfo_min_freq",
+                    "408000\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu2/cpufreq/cpuinfo_max_freq",
+                    "2400000\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu3/topology/physical_package_id",
+                    "0\n",
+                ),
+                ("sys/devices/system/cpu/cpu3/topology/core_id", "3\n"),
+                ("sys/devices/system/cpu/cpu3/cpu_capacity", "1024\n"),
+                (
+                    "sys/devices/system/cpu/cpu3/cpufreq/cpuinfo_min_freq",
+                    "408000\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu3/cpufreq/cpuinfo_max_freq",
+                    "2400000\n",
+                ),
+                ("sys/devices/system/cpu/online", "0-3\n"),
+                (
+                    "proc/cpuinfo",
+                    "processor\t: 0\nCPU implementer\t: 0x41\nCPU part\t: 0xd05\n\n\
+                     processor\t: 1\nCPU implementer\t: 0x41\nCPU part\t: 0xd05\n\n\
+                     processor\t: 2\nCPU implementer\t: 0x41\nCPU part\t: 0xd0b\n\n\
+                     processor\t: 3\nCPU implementer\t: 0x41\nCPU part\t: 0xd0b\n",
+                ),
+            ],
+        );
+
+        let readout = LinuxGeneralReadout::with_root(root.path());
+        let topology = readout.cpu_topology().unwrap();
+        assert_eq!(topology.hybrid_summary().as_deref(), Some("2P + 2E"));
+        assert_eq!(
+            topology.clusters[0],
+            CpuCluster {
+                core_type: CoreType::Performance,
+                cpus: vec![2, 3],
+                cores: 2,
+                model: Some("Cortex-A76".to_owned()),
+                min_frequency: Some(408.0),
+                max_frequency: Some(2400.0),
+            }
+        );
+        assert_eq!(topology.clusters[1].cpus, [0, 1]);
+        assert_eq!(topology.clusters[1].model.as_deref(), Some("Cortex-A55"));
+
+        // Intel lists its P-cores and E-cores, including offline ones.
+        fs::write(root.path().join("sys/devices/system/cpu/online"), "0-2\n").unwrap();
+        for (pmu, cpus) in [("cpu_core", "0,3"), ("cpu_atom", "1-2")] {
+            fs::create_dir_all(root.path().join("sys/devices").join(pmu)).unwrap();
+            fs::write(root.path().join("sys/devices").join(pmu).join("cpus"), cpus).unwrap();
+        }
+
+        let topology = readout.cpu_topology().unwrap();
+        assert_eq!(topology.hybrid_summary().as_deref(), Some("1P + 2E"));
+        assert_eq!(topology.clusters[1].cpus, [1, 2]);
+
+        // With its E-cores offline, the CPU is no longer hybrid.
+        fs::write(root.path().join("sys/devices/system/cpu/online"), "0\n").unwrap();
+        let topology = readout.cpu_topology().unwrap();
+        assert!(topology.clusters.is_empty());
+        assert_eq!(topology.hybrid_summary(), None);
+
+        // The preferred cores of amd-pstate boost higher, but are no separate cluster.
+        let root = Fixture::new(
+            "uniform_clusters",
+            &[
+                (
+                    "sys/devices/system/cpu/cpu0/topology/physical_package_id",
+                    "0\n",
+                ),
+                ("sys/devices/system/cpu/cpu0/topology/core_id", "0\n"),
+                (
+                    "sys/devices/system/cpu/cpu0/cpufreq/scaling_driver",
+                    "amd-pstate-epp\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu0/cpufreq/cpuinfo_max_freq",
+                    "4850000\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu1/topology/physical_package_id",
+                    "0\n",
+                ),
+                ("sys/devices/system/cpu/cpu1/topology/core_id", "1\n"),
+                (
+                    "sys/devices/system/cpu/cpu1/cpufreq/scaling_driver",
+                    "amd-pstate-epp\n",
+                ),
+                (
+                    "sys/devices/system/cpu/cpu1/cpufreq/cpuinfo_max_freq",
+                    "4600000\n",
+                ),
+                ("sys/devices/system/cpu/online", "0-1\n"),
+                (
+                    "proc/cpuinfo",
+                    "processor\t: 0\nvendor_id\t: AuthenticAMD\n\n\
+                     processor\t: 1\nvendor_id\t: AuthenticAMD\n",
+                ),
+            ],
+        );
+
+        let topology = LinuxGeneralReadout::with_root(root.path())
+            .cpu_topology()
+            .unwrap();
+        assert!(topology.clusters.is_empty());
+        assert_eq!(topology.hybrid_summary(), None);
+    }
+
+    #[test]
+    fn test_cpu_features() {
+        let v3 = "fpu cx8 cmov mmx fxsr sse sse2 syscall lm pni ssse3 cx16 sse4_1 sse4_2 popcnt \
+                  lahf_lm abm avx avx2 bmi1 bmi2 f16c fma movbe xsave aes";
+        let cpuinfo = format!(
+            "processor\t: 0\nflags\t\t: {v3} avx512f avx512bw avx512cd avx512dq avx512vl\n\n\
+             processor\t: 1\nflags\t\t: {v3}\n"
+        );
+        let root = Fixture::new(
+            "features",
+            &[
+                ("proc/cpuinfo", &cpuinfo),
+                (
+                    "sys/devices/system/cpu/vulnerabilities/meltdown",
+                    "Not affected\n",
+                ),
+                (
+                    "sys/devices/system/cpu/vulnerabilities/itlb_multihit",
+                    "KVM: Mitigation: VMX disabled\n",
+                ),
+                (
+                    "sys/devices/system/cpu/vulnerabilities/mds",
+                    "Vulnerable: Clear CPU buffers attempted, no microcode; SMT vulnerable\n",
+                ),
+                (
+                    "sys/devices/system/cpu/vulnerabilities/mmio_stale_data",
+                    "Unknown: No mitigations\n",
+                ),
+                (
+                    "sys/devices/system/cpu/vulnerabilities/spectre_v2",
+                    "Vulnerable, IBPB: disabled, STIBP: disabled\n",
+                ),
+            ],
+        );
+
+        let readout = LinuxGeneralReadout::with_root(root.path());
+        let features = readout.cpu_features().unwrap();
+        assert!(features.contains("aes"));
+        assert!(!features.contains("avx512f"));
+        assert_eq!(features.x86_64_level(), Some(3));
+
+        let vulnerabilities = readout.cpu_vulnerabilities().unwrap();
+        let status = |name: &str| {
+            vulnerabilities
+                .iter()
+                .find(|v| v.name == name)
+                .map(|v| v.status.clone())
+                .unwrap()
+        };
+        assert_eq!(vulnerabilities.len(), 5);
+        assert_eq!(status("meltdown"), VulnerabilityStatus::NotAffected);
+        assert_eq!(
+            status("itlb_multihit"),
+            VulnerabilityStatus::Mitigated("VMX disabled".to_owned())
+        );
+        assert_eq!(
+            status("mds"),
+            VulnerabilityStatus::Vulnerable(Some(
+                "Clear CPU buffers attempted, no microcode; SMT vulnerable".to_owned()
+            ))
+        );
+        assert_eq!(
+            status("mmio_stale_data"),
+            VulnerabilityStatus::Unknown("No mitigations".to_owned())
+        );
+        assert_eq!(
+            status("spectre_v2"),
+            VulnerabilityStatus::Vulnerable(Some("IBPB: disabled, STIBP: disabled".to_owned()))
+        );
+
+        // ARM reports "Features" and RISC-V "isa" instead of "flags".
+        fs::write(
+            root.path().join("proc/cpuinfo"),
+            "processor\t: 0\nisa\t\t: rv64imafdc_zicsr_zifencei\n",
+        )
+        .unwrap();
+        let features = readout.cpu_features().unwrap();
+        assert!(features.contains("d") && features.contains("zicsr"));
+        assert_eq!(features.x86_64_level(), None);
+    }
+}
@@ -0,0 +1,40 @@
+//! This module provides [`Fixture`], a filesystem tree that the tests of the Linux readouts
+//! point their root at.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A tree of files under the system's temporary directory, which is removed again when the
+/// fixture is dropped, even if the test panics.
+pub(super) struct Fixture {
+    root: PathBuf,
+}
+
+impl Fixture {
+    /// Creates a fresh tree named after `name`, populated with the given `(path, contents)`
+    /// pairs.
+    pub(super) fn new(name: &str, files: &[(&str, &str)]) -> Self {
+        let root =
+            std::env::temp_dir().join(format!("libmacchina-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        for (path, contents) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        Fixture { root }
+    }
+
+    /// Returns the root of the tree.
+    pub(super) fn path(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
@@ -0,0 +1,226 @@
+use super::{numbered_entries, parse_file, read_rooted};
+use crate::extra::get_entries;
+use crate::traits::{Sensor, SensorChip, SensorKind};
+use std::path::Path;
+
+/// Reads the sensors of a single hwmon device. Values are reported in millidegrees Celsius,
+/// RPM and millivolts respectively.
+fn read_hwmon_sensors(dir: &Path) -> Vec<Sensor> {
+    let mut inputs: Vec<(SensorKind, u32, String)> = get_entries(dir)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|entry| {
+            let name = entry.file_name()?.to_str()?;
+            let channel = name.strip_suffix("_input")?;
+
+            let (kind, number) = if let Some(n) = channel.strip_prefix("temp") {
+                (SensorKind::Temperature, n)
+            } else if let Some(n) = channel.strip_prefix("fan") {
+                (SensorKind::Fan, n)
+            } else if let Some(n) = channel.strip_prefix("in") {
+                (SensorKind::Voltage, n)
+            } else {
+                return None;
+            };
+
+            Some((kind, number.parse().ok()?, channel.to_owned()))
+        })
+        .collect();
+
+    inputs.sort_by_key(|(kind, number, _)| (*kind as u8, *number));
+
+    inputs
+        .into_iter()
+        .filter_map(|(kind, _, channel)| {
+            let scale = match kind {
+                SensorKind::Fan => 1.0,
+                _ => 1000.0,
+            };
+            let read = |attribute: &str| {
+                parse_file::<f64>(&dir.join(format!("{channel}_{attribute}")))
+                    .ok()
+                    .map(|v| v / scale)
+            };
+
+            Some(Sensor {
+                label: read_rooted(dir, &format!("{channel}_label"))
+                    .unwrap_or_else(|_| channel.clone()),
+                kind,
+                value: read("input")?,
+                max: read("max"),
+                critical: read("crit"),
+            })
+        })
+        .collect()
+}
+
+/// Reads every chip under `sys/class/hwmon`.
+pub(super) fn read_hwmon_chips(root: &Path) -> Vec<SensorChip> {
+    numbered_entries(&root.join("sys/class/hwmon"), "hwmon")
+        .into_iter()
+        .filter_map(|hwmon| {
+            // Older drivers keep their attributes in the device directory.
+            let dir = if hwmon.join("name").exists() {
+                hwmon
+            } else {
+                hwmon.join("device")
+            };
+
+            let sensors = read_hwmon_sensors(&dir);
+            if sensors.is_empty() {
+                return None;
+            }
+
+            Some(SensorChip {
+                name: read_rooted(&dir, "name").ok()?,
+                sensors,
+            })
+        })
+        .collect()
+}
+
+/// Reads every zone under `sys/class/thermal`, grouped by their type. Zones whose type is
+/// already in `known` are skipped, as the kernel also exposes most zones through hwmon.
+pub(super) fn read_thermal_zones(root: &Path, known: &[SensorChip]) -> Vec<SensorChip> {
+    let mut chips: Vec<SensorChip> = Vec::new();
+
+    for zone in numbered_entries(&root.join("sys/class/thermal"), "thermal_zone") {
+        let Ok(name) = read_rooted(&zone, "type") else {
+            continue;
+        };
+
+        if known.iter().any(|chip| chip.name == name) {
+            continue;
+        }
+
+        let Ok(value) = parse_file::<f64>(&zone.join("temp")) else {
+            continue;
+        };
+
+        // Trip points are numbered consecutively from zero.
+        let trip_point = |kind: &str| {
+            let trip_type = |i: u32| zone.join(format!("trip_point_{i}_type"));
+
+            (0..)
+                .take_while(|&i| trip_type(i).exists())
+                .find(|&i| {
+                    read_rooted(&zone, &format!("trip_point_{i}_type"))
+                        .is_ok_and(|trip| trip == kind)
+                })
+                .and_then(|i| parse_file::<f64>(&zone.join(format!("trip_point_{i}_temp"))).ok())
+                .map(|v| v / 1000.0)
+        };
+
+        let sensor = Sensor {
+            label: zone
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            kind: SensorKind::Temperature,
+            value: value / 1000.0,
+            max: trip_point("hot").or_else(|| trip_point("passive")),
+            critical: trip_point("critical"),
+        };
+
+        match chips.iter_mut().find(|chip| chip.name == name) {
+            Some(chip) => chip.sensors.push(sensor),
+            None => chips.push(SensorChip {
+                name,
+                sensors: vec![sensor],
+            }),
+        }
+    }
+
+    chips
+}
+
+/// Picks the temperature of the CPU package from `chips`, preferring the sensors that
+/// describe the whole package over those of single cores.
+pub(super) fn cpu_temperature(chips: &[SensorChip]) -> Option<f64> {
+    // (chip, label), where an empty label matches the first sensor of the chip
+    const CANDIDATES: &[(&str, &str)] = &[
+        ("coretemp", "Package id 0"),
+        ("k10temp", "Tdie"),
+        ("k10temp", "Tctl"),
+        ("zenpower", "Tdie"),
+        ("x86_pkg_temp", ""),
+        ("cpu_thermal", ""),
+        ("cpu-thermal", ""),
+        ("soc_thermal", ""),
+    ];
+
+    CANDIDATES.iter().find_map(|(chip, label)| {
+        let chip = chips.iter().find(|c| c.name == *chip)?;
+        chip.sensors
+            .iter()
+            .filter(|s| s.kind == SensorKind::Temperature)
+            .find(|s| label.is_empty() || s.label == *label)
+            .map(|s| s.value)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::linux::fixture::Fixture;
+    use crate::linux::LinuxSensorReadout;
+    use crate::traits::*;
+
+    #[test]
+    fn test_sensors() {
+        let root = Fixture::new(
+            "sensors",
+            &[
+                ("sys/class/hwmon/hwmon2/name", "nvme\n"),
+                ("sys/class/hwmon/hwmon2/temp1_input", "38850\n"),
+                ("sys/class/hwmon/hwmon2/temp1_label", "Composite\n"),
+                ("sys/class/hwmon/hwmon2/temp1_max", "81850\n"),
+                ("sys/class/hwmon/hwmon2/temp1_crit", "84850\n"),
+                ("sys/class/hwmon/hwmon10/name", "k10temp\n"),
+                ("sys/class/hwmon/hwmon10/temp1_input", "51250\n"),
+                ("sys/class/hwmon/hwmon10/temp1_label", "Tctl\n"),
+                ("sys/class/hwmon/hwmon10/fan1_input", "1200\n"),
+                ("sys/class/hwmon/hwmon10/in0_input", "1100\n"),
+                ("sys/class/hwmon/hwmon3/name", "acpitz\n"),
+                ("sys/class/hwmon/hwmon3/temp1_input", "27800\n"),
+                ("sys/class/thermal/thermal_zone0/type", "acpitz\n"),
+                ("sys/class/thermal/thermal_zone0/temp", "27800\n"),
+                ("sys/class/thermal/thermal_zone1/type", "iwlwifi_1\n"),
+                ("sys/class/thermal/thermal_zone1/temp", "41000\n"),
+                (
+                    "sys/class/thermal/thermal_zone1/trip_point_0_type",
+                    "critical\n",
+                ),
+                (
+                    "sys/class/thermal/thermal_zone1/trip_point_0_temp",
+                    "118000\n",
+                ),
+            ],
+        );
+
+        let sensors = LinuxSensorReadout::with_root(root.path());
+        let chips = sensors.chips().unwrap();
+        let names: Vec<&str> = chips.iter().map(|chip| chip.name.as_str()).collect();
+        assert_eq!(names, ["nvme", "acpitz", "k10temp", "iwlwifi_1"]);
+
+        let nvme = &chips[0].sensors[0];
+        assert_eq!(nvme.label, "Composite");
+        assert_eq!(
+            (nvme.value, nvme.max, nvme.critical),
+            (38.85, Some(81.85), Some(84.85))
+        );
+
+        let k10temp = &chips[2].sensors;
+        assert_eq!(k10temp[1].kind, SensorKind::Fan);
+        assert_eq!(
+            (k10temp[1].label.as_str(), k10temp[1].value),
+            ("fan1", 1200.0)
+        );
+        assert_eq!(
+            (k10temp[2].kind, k10temp[2].value),
+            (SensorKind::Voltage, 1.1)
+        );
+
+        assert_eq!(chips[3].sensors[0].critical, Some(118.0));
+        assert_eq!(sensors.cpu_temperature().unwrap(), 51.25);
+    }
+}
@@ -0,0 +1,556 @@
+use super::cpu::parse_cpu_list;
+use super::{numbered_entries, parse_file, read_rooted};
+use crate::extra::get_entries;
+use crate::shared;
+use crate::traits::{
+    HugePagePool, HugePages, NumaNode, Pressure, PressureStall, ReadoutError, ReadoutErrorKind,
+    SwapDevice, SwapInfo, SwapKind, SystemPressure, TransparentHugePages, VmStats, ZramDevice,
+    Zswap,
+};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+fn parse_error(path: &Path, line: &str) -> ReadoutError {
+    ReadoutError::failed(ReadoutErrorKind::ParseError)
+        .with_path(path)
+        .with_message(format!("Could not parse '{line}'"))
+}
+
+/// Parses a line of a pressure file such as
+/// `some avg10=0.31 avg60=0.12 avg300=0.04 total=18236501`, where `total` is in microseconds.
+fn parse_stall(line: &str) -> Option<PressureStall> {
+    let mut stall = PressureStall::default();
+
+    for (key, value) in line
+        .split_whitespace()
+        .skip(1)
+        .filter_map(|f| f.split_once('='))
+    {
+        match key {
+            "avg10" => stall.avg10 = value.parse().ok()?,
+            "avg60" => stall.avg60 = value.parse().ok()?,
+            "avg300" => stall.avg300 = value.parse().ok()?,
+            "total" => stall.total = Duration::from_micros(value.parse().ok()?),
+            _ => {}
+        }
+    }
+
+    Some(stall)
+}
+
+/// Reads one of the files in `proc/pressure`. Returns `None` if the kernel doesn't track the
+/// resource, in which case the file is missing or can't be read.
+fn read_pressure(root: &Path, resource: &str) -> Result<Option<Pressure>, ReadoutError> {
+    let path = root.join("proc/pressure").join(resource);
+    let Ok(content) = read_rooted(root, &format!("proc/pressure/{resource}")) else {
+        return Ok(None);
+    };
+
+    let mut some = None;
+    let mut full = None;
+
+    for line in content.lines() {
+        let stall = || parse_stall(line).ok_or_else(|| parse_error(&path, line));
+
+        if line.starts_with("some ") {
+            some = Some(stall()?);
+        } else if line.starts_with("full ") {
+            full = Some(stall()?);
+        }
+    }
+
+    match some {
+        Some(some) => Ok(Some(Pressure { some, full })),
+        None => Err(parse_error(&path, content.trim())),
+    }
+}
+
+/// Reads the pressure stall information in `proc/pressure`.
+pub(super) fn pressure(root: &Path) -> Result<SystemPressure, ReadoutError> {
+    let pressure = SystemPressure {
+        cpu: read_pressure(root, "cpu")?,
+        memory: read_pressure(root, "memory")?,
+        io: read_pressure(root, "io")?,
+    };
+
+    if pressure == SystemPressure::default() {
+        return Err(ReadoutError::MetricNotAvailable);
+    }
+
+    Ok(pressure)
+}
+
+/// Reads the counters of interest in `proc/vmstat`.
+pub(super) fn vm_stats(root: &Path) -> Result<VmStats, ReadoutError> {
+    let path = root.join("proc/vmstat");
+    let content = shared::read_file(&path)?;
+    let mut stats = VmStats::default();
+
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(' ') else {
+            continue;
+        };
+
+        let value: u64 = value.trim().parse().map_err(|_| parse_error(&path, line))?;
+
+        match key {
+            "oom_kill" => stats.oom_kills = Some(value),
+            "pgmajfault" => stats.major_faults = value,
+            "pswpin" => stats.swap_ins = value,
+            "pswpout" => stats.swap_outs = value,
+            // Kernels before 4.8 keep a single counter, later ones one per memory zone.
+            "allocstall" => stats.allocation_stalls += value,
+            _ if key.starts_with("allocstall_") => stats.allocation_stalls += value,
+            _ => {}
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Undoes the octal escapes the kernel uses for whitespace in paths, _e.g._ `\040` for a space.
+fn unescape(path: &str) -> String {
+    let mut unescaped = String::with_capacity(path.len());
+    let mut rest = path;
+
+    while let Some(i) = rest.find('\\') {
+        unescaped.push_str(&rest[..i]);
+        let escape = rest.get(i + 1..i + 4);
+
+        match escape.and_then(|octal| u8::from_str_radix(octal, 8).ok()) {
+            Some(byte) => {
+                unescaped.push(byte as char);
+                rest = &rest[i + 4..];
+            }
+            None => {
+                unescaped.push('\\');
+                rest = &rest[i + 1..];
+            }
+        }
+    }
+
+    unescaped.push_str(rest);
+    unescaped
+}
+
+/// Reads the swap devices listed in `proc/swaps`.
+fn read_swap_devices(root: &Path) -> Result<Vec<SwapDevice>, ReadoutError> {
+    let path = root.join("proc/swaps");
+    let content = shared::read_file(&path)?;
+
+    // The first line is a header.
+    content
+        .lines()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [name, kind, size, used, priority] = fields[..] else {
+                return Err(parse_error(&path, line));
+            };
+
+            let number = |field: &str| field.parse().map_err(|_| parse_error(&path, line));
+
+            Ok(SwapDevice {
+                path: PathBuf::from(unescape(name)),
+                kind: match kind {
+                    "file" => SwapKind::File,
+                    _ => SwapKind::Partition,
+                },
+                size: number(size)?,
+                used: number(used)?,
+                priority: priority.parse().map_err(|_| parse_error(&path, line))?,
+            })
+        })
+        .collect()
+}
+
+/// Reads every zram device that has been set up under `sys/block`.
+fn read_zram_devices(root: &Path) -> Vec<ZramDevice> {
+    numbered_entries(&root.join("sys/block"), "zram")
+        .into_iter()
+        .filter_map(|dir| {
+            // orig_data_size compr_data_size mem_used_total mem_limit mem_used_max ..., in bytes
+            let stat: Vec<u64> = read_rooted(&dir, "mm_stat")
+                .ok()?
+                .split_whitespace()
+                .map_while(|field| field.parse().ok())
+                .collect();
+
+            let disk_size = parse_file::<u64>(&dir.join("disksize")).ok()?;
+            if disk_size == 0 || stat.len() < 3 {
+                return None;
+            }
+
+            // _e.g._ `lzo lzo-rle [zstd]`, where the selected algorithm is in brackets
+            let algorithm = read_rooted(&dir, "comp_algorithm")
+                .ok()
+                .and_then(|algorithms| {
+                    let (_, selected) = algorithms.split_once('[')?;
+                    Some(selected.split_once(']')?.0.to_owned())
+                });
+
+            Some(ZramDevice {
+                name: dir.file_name()?.to_str()?.to_owned(),
+                algorithm,
+                disk_size: disk_size / 1024,
+                original: stat[0] / 1024,
+                compressed: stat[1] / 1024,
+                memory_used: stat[2] / 1024,
+            })
+        })
+        .collect()
+}
+
+/// Reads the parameters of zswap, and the size of its pool from debugfs, which usually only root
+/// may read, or from `proc/meminfo` on newer kernels.
+fn read_zswap(root: &Path) -> Option<Zswap> {
+    let parameters = root.join("sys/module/zswap/parameters");
+    let parameter = |name: &str| read_rooted(&parameters, name).ok();
+
+    let enabled = parameter("enabled")?;
+    let meminfo = shared::read_meminfo_at(root).ok();
+    let debugfs = root.join("sys/kernel/debug/zswap");
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as u64;
+
+    Some(Zswap {
+        enabled: enabled == "Y" || enabled == "1",
+        compressor: parameter("compressor"),
+        zpool: parameter("zpool"),
+        max_pool_percent: parameter("max_pool_percent").and_then(|p| p.parse().ok()),
+        pool_size: parse_file::<u64>(&debugfs.join("pool_total_size"))
+            .ok()
+            .map(|bytes| bytes / 1024)
+            .or_else(|| meminfo?.zswap),
+        stored: parse_file::<u64>(&debugfs.join("stored_pages"))
+            .ok()
+            .map(|pages| pages * page_size / 1024)
+            .or_else(|| meminfo?.zswapped),
+    })
+}
+
+/// Reads the swap devices, zram devices and zswap.
+pub(super) fn swap(root: &Path) -> Result<SwapInfo, ReadoutError> {
+    Ok(SwapInfo {
+        devices: read_swap_devices(root)?,
+        zram: read_zram_devices(root),
+        zswap: read_zswap(root),
+    })
+}
+
+/// Reads the memory of a NUMA node from its `meminfo`, whose lines are of the form
+/// `Node 0 MemTotal: 16318460 kB`. Returns the total, free and used memory.
+fn read_node_meminfo(dir: &Path) -> Result<(u64, u64, u64), ReadoutError> {
+    let path = dir.join("meminfo");
+    let content = shared::read_file(&path)?;
+
+    let field = |key: &str| -> Result<Option<u64>, ReadoutError> {
+        let Some(line) = content
+            .lines()
+            .find(|line| line.split_whitespace().nth(2) == Some(key))
+        else {
+            return Ok(None);
+        };
+
+        line.split_whitespace()
+            .nth(3)
+            .and_then(|value| value.parse().ok())
+            .map(Some)
+            .ok_or_else(|| parse_error(&path, line))
+    };
+
+    let total = field("MemTotal:")?.ok_or_else(|| parse_error(&path, content.trim()))?;
+    let free = field("MemFree:")?.ok_or_else(|| parse_error(&path, content.trim()))?;
+    let used = field("MemUsed:")?.unwrap_or(total.saturating_sub(free));
+
+    Ok((total, free, used))
+}
+
+/// Reads every NUMA node under `sys/devices/system/node`.
+pub(super) fn numa_nodes(root: &Path) -> Result<Vec<NumaNode>, ReadoutError> {
+    let nodes = numbered_entries(&root.join("sys/devices/system/node"), "node");
+    if nodes.is_empty() {
+        return Err(ReadoutError::MetricNotAvailable);
+    }
+
+    nodes
+        .iter()
+        .filter_map(|dir| {
+            let id = dir
+                .file_name()?
+                .to_str()?
+                .strip_prefix("node")?
+                .parse()
+                .ok()?;
+            Some((id, dir))
+        })
+        .map(|(id, dir)| {
+            let (total, free, used) = read_node_meminfo(dir)?;
+
+            Ok(NumaNode {
+                id,
+                total,
+                free,
+                used,
+                cpus: read_rooted(dir, "cpulist")
+                    .ok()
+                    .and_then(|list| parse_cpu_list(&list))
+                    .unwrap_or_default(),
+                distances: read_rooted(dir, "distance")
+                    .map(|d| {
+                        d.split_whitespace()
+                            .filter_map(|d| d.parse().ok())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Reads a pool of huge pages from its directory, _e.g._ `hugepages-2048kB`.
+fn read_huge_page_pool(dir: &Path) -> Result<HugePagePool, ReadoutError> {
+    let name = dir.file_name().unwrap_or_default().to_string_lossy();
+    let size = name
+        .strip_prefix("hugepages-")
+        .and_then(|size| size.strip_suffix("kB")?.parse().ok())
+        .ok_or_else(|| parse_error(dir, &name))?;
+
+    let count = |name: &str| parse_file::<u64>(&dir.join(name));
+
+    Ok(HugePagePool {
+        size,
+        total: count("nr_hugepages")?,
+        free: count("free_hugepages")?,
+        reserved: count("resv_hugepages")?,
+        surplus: count("surplus_hugepages")?,
+    })
+}
+
+/// Reads the pools of huge pages under `sys/kernel/mm/hugepages`, and the transparent huge page
+/// mode.
+pub(super) fn huge_pages(root: &Path) -> Result<HugePages, ReadoutError> {
+    let mm = root.join("sys/kernel/mm");
+
+    let mut pools: Vec<HugePagePool> = get_entries(&mm.join("hugepages"))
+        .unwrap_or_default()
+        .iter()
+        .map(|dir| read_huge_page_pool(dir))
+        .collect::<Result<_, _>>()?;
+
+    pools.sort_by_key(|pool| pool.size);
+
+    // _e.g._ `always [madvise] never`, where the selected mode is in brackets
+    let transparent = read_rooted(&mm, "transparent_hugepage/enabled")
+        .ok()
+        .and_then(|modes| match modes.split_once('[')?.1.split_once(']')?.0 {
+            "always" => Some(TransparentHugePages::Always),
+            "madvise" => Some(TransparentHugePages::Madvise),
+            "never" => Some(TransparentHugePages::Never),
+            _ => None,
+        });
+
+    if pools.is_empty() && transparent.is_none() {
+        return Err(ReadoutError::MetricNotAvailable);
+    }
+
+    Ok(HugePages { pools, transparent })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::linux::fixture::Fixture;
+    use crate::linux::LinuxMemoryReadout;
+    use crate::traits::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    #[test]
+    fn test_memory_pressure() {
+        let root = Fixture::new(
+            "pressure",
+            &[
+                (
+                    "proc/pressure/cpu",
+                    "some avg10=1.50 avg60=0.75 avg300=0.20 total=2500000\n",
+                ),
+                (
+                    "proc/pressure/memory",
+                    "some avg10=12.04 avg60=8.31 avg300=3.10 total=91000000\n\
+                     full avg10=9.80 avg60=6.02 avg300=2.25 total=64000000\n",
+                ),
+                (
+                    "proc/vmstat",
+                    "nr_free_pages 20731\npgmajfault 5821\npswpin 120\npswpout 4410\n\
+                     allocstall_dma32 2\nallocstall_normal 37\noom_kill 1\n",
+                ),
+            ],
+        );
+
+        let memory = LinuxMemoryReadout::with_root(root.path());
+        let pressure = memory.pressure().unwrap();
+        assert_eq!(pressure.cpu.unwrap().some.avg10, 1.5);
+        assert_eq!(pressure.cpu.unwrap().full, None);
+        assert_eq!(
+            pressure.memory.unwrap().full.unwrap().total,
+            Duration::from_secs(64)
+        );
+        assert_eq!(pressure.io, None);
+
+        let stats = memory.vm_stats().unwrap();
+        assert_eq!(
+            stats,
+            VmStats {
+                oom_kills: Some(1),
+                major_faults: 5821,
+                swap_ins: 120,
+                swap_outs: 4410,
+                allocation_stalls: 39,
+            }
+        );
+
+        fs::write(root.path().join("proc/pressure/io"), "some avg10=x\n").unwrap();
+        assert_eq!(
+            memory.pressure().unwrap_err().kind(),
+            Some(ReadoutErrorKind::ParseError)
+        );
+    }
+
+    #[test]
+    fn test_swap() {
+        let root = Fixture::new(
+            "swap",
+            &[
+                (
+                    "proc/swaps",
+                    "Filename\t\t\t\tType\t\tSize\t\tUsed\t\tPriority\n\
+                     /dev/zram0                              partition\t8388604\t\t524288\t\t100\n\
+                     /var/swap\\040file                       file\t\t2097148\t\t0\t\t-2\n",
+                ),
+                ("sys/block/zram0/disksize", "8589930496\n"),
+                ("sys/block/zram0/comp_algorithm", "lzo lzo-rle [zstd]\n"),
+                (
+                    "sys/block/zram0/mm_stat",
+                    "536870912 134217728 142606336 0 142606336 1024 0 12 0\n",
+                ),
+                ("sys/block/zram1/disksize", "0\n"),
+                ("sys/module/zswap/parameters/enabled", "N\n"),
+                ("sys/module/zswap/parameters/compressor", "lzo\n"),
+                ("sys/module/zswap/parameters/max_pool_percent", "20\n"),
+            ],
+        );
+
+        let swap = LinuxMemoryReadout::with_root(root.path()).swap().unwrap();
+        assert_eq!(
+            swap.devices[1],
+            SwapDevice {
+                path: PathBuf::from("/var/swap file"),
+                kind: SwapKind::File,
+                size: 2097148,
+                used: 0,
+                priority: -2,
+            }
+        );
+        assert_eq!(swap.devices[0].used, 524288);
+
+        assert_eq!(swap.zram.len(), 1);
+        assert_eq!(swap.zram[0].algorithm.as_deref(), Some("zstd"));
+        assert_eq!(swap.zram[0].original, 524288);
+        assert_eq!(swap.zram[0].compression_ratio(), Some(4.0));
+
+        let zswap = swap.zswap.unwrap();
+        assert!(!zswap.enabled);
+        assert_eq!(zswap.max_pool_percent, Some(20));
+        assert_eq!((zswap.zpool, zswap.pool_size), (None, None));
+    }
+
+    #[test]
+    fn test_numa_and_huge_pages() {
+        let root = Fixture::new(
+            "numa",
+            &[
+                (
+                    "sys/devices/system/node/node0/meminfo",
+                    "Node 0 MemTotal:       65843484 kB\nNode 0 MemFree:        40212064 kB\n\
+                     Node 0 MemUsed:        25631420 kB\n",
+                ),
+                ("sys/devices/system/node/node0/cpulist", "0-15,32-47\n"),
+                ("sys/devices/system/node/node0/distance", "10 21\n"),
+                (
+                    "sys/devices/system/node/node1/meminfo",
+                    "Node 1 MemTotal:       66060788 kB\nNode 1 MemFree:        61940312 kB\n",
+                ),
+                ("sys/devices/system/node/node1/cpulist", "16-31,48-63\n"),
+                ("sys/devices/system/node/node1/distance", "21 10\n"),
+                (
+                    "sys/kernel/mm/hugepages/hugepages-2048kB/nr_hugepages",
+                    "512\n",
+                ),
+                (
+                    "sys/kernel/mm/hugepages/hugepages-2048kB/free_hugepages",
+                    "128\n",
+                ),
+                (
+                    "sys/kernel/mm/hugepages/hugepages-2048kB/resv_hugepages",
+                    "64\n",
+                ),
+                (
+                    "sys/kernel/mm/hugepages/hugepages-2048kB/surplus_hugepages",
+                    "0\n",
+                ),
+                (
+                    "sys/kernel/mm/hugepages/hugepages-1048576kB/nr_hugepages",
+                    "4\n",
+                ),
+                (
+                    "sys/kernel/mm/hugepages/hugepages-1048576kB/free_hugepages",
+                    "4\n",
+                ),
+                (
+                    "sys/kernel/mm/hugepages/hugepages-1048576kB/resv_hugepages",
+                    "0\n",
+                ),
+                (
+                    "sys/kernel/mm/hugepages/hugepages-1048576kB/surplus_hugepages",
+                    "0\n",
+                ),
+                (
+                    "sys/kernel/mm/transparent_hugepage/enabled",
+                    "always [madvise] never\n",
+                ),
+            ],
+        );
+
+        let memory = LinuxMemoryReadout::with_root(root.path());
+        let nodes = memory.numa_nodes().unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].used, 25631420);
+        assert_eq!(nodes[0].cpus.len(), 32);
+        assert_eq!(
+            nodes[1],
+            NumaNode {
+                id: 1,
+                total: 66060788,
+                free: 61940312,
+                used: 4120476,
+                cpus: (16..32).chain(48..64).collect(),
+                distances: vec![21, 10],
+            }
+        );
+
+        let huge_pages = memory.huge_pages().unwrap();
+        assert_eq!(
+            huge_pages.pools[0],
+            HugePagePool {
+                size: 2048,
+                total: 512,
+                free: 128,
+                reserved: 64,
+                surplus: 0,
+            }
+        );
+        assert_eq!(huge_pages.pools[1].size, 1048576);
+        assert_eq!(huge_pages.transparent, Some(TransparentHugePages::Madvise));
+    }
+}
@@ -1,8 +1,16 @@
 #![allow(clippy::unnecessary_cast)]
+mod cgroup;
+mod cpu;
+#[cfg(test)]
+mod fixture;
+mod hwmon;
+mod memory;
 mod pci_devices;
 mod sysinfo_ffi;
 
 use self::pci_devices::get_pci_devices;
+use crate::capabilities::{Capability, Prerequisite, ReadoutKind};
+use crate::command;
 use crate::extra;
 use crate::extra::get_entries;
 use crate::extra::path_extension;
@@ -17,260 +25,600 @@ use std::fs::read_dir;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
-use sysctl::{Ctl, Sysctl};
+use std::time::Duration;
 use sysinfo_ffi::sysinfo;
 
 impl From<sqlite::Error> for ReadoutError {
     fn from(e: sqlite::Error) -> Self {
-        ReadoutError::Other(e.to_string())
+        ReadoutError::from_source(ReadoutErrorKind::Other, e)
     }
 }
 
+/// The filesystem root readouts are resolved against unless another one is
+/// given through `with_root`.
+const DEFAULT_ROOT: &str = "/";
+
+/// Every Linux readout can be constructed against an alternate filesystem root
+/// through its `with_root` constructor, e.g. a chroot, a mounted container image
+/// or a fixture tree. Paths such as `/proc/meminfo` or `/sys/class/power_supply`
+/// are then looked up relative to that root.
+///
+/// Values that are obtained through system calls (`sysinfo`, `statfs`, `getpwuid`)
+/// or from the environment of the calling process always describe the running
+/// kernel and process, regardless of the configured root.
 pub struct LinuxKernelReadout {
-    os_release_ctl: Option<Ctl>,
-    os_type_ctl: Option<Ctl>,
+    root: PathBuf,
 }
 
 pub struct LinuxGeneralReadout {
-    hostname_ctl: Option<Ctl>,
+    root: PathBuf,
     sysinfo: sysinfo,
 }
 
 pub struct LinuxMemoryReadout {
-    sysinfo: sysinfo,
+    root: PathBuf,
 }
 
-pub struct LinuxBatteryReadout;
-pub struct LinuxProductReadout;
-pub struct LinuxPackageReadout;
-pub struct LinuxNetworkReadout;
+pub struct LinuxBatteryReadout {
+    root: PathBuf,
+}
 
-impl BatteryReadout for LinuxBatteryReadout {
-    fn new() -> Self {
-        LinuxBatteryReadout
+pub struct LinuxProductReadout {
+    root: PathBuf,
+}
+
+pub struct LinuxPackageReadout {
+    root: PathBuf,
+}
+
+pub struct LinuxNetworkReadout {
+    root: PathBuf,
+}
+
+pub struct LinuxSensorReadout {
+    root: PathBuf,
+}
+
+/// Reads a single-line file relative to `root`, stripping the trailing newline.
+fn read_rooted(root: &Path, path: &str) -> Result<String, ReadoutError> {
+    Ok(extra::pop_newline(shared::read_file(root.join(path))?))
+}
+
+/// Returns the entries of `dir` whose name is `prefix` followed by a number, ordered by that
+/// number, so that `hwmon10` comes after `hwmon2`.
+fn numbered_entries(dir: &Path, prefix: &str) -> Vec<PathBuf> {
+    let mut entries: Vec<(u32, PathBuf)> = get_entries(dir)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|entry| {
+            let name = entry.file_name()?.to_str()?;
+            let number = name.strip_prefix(prefix)?.parse().ok()?;
+            Some((number, entry))
+        })
+        .collect();
+
+    entries.sort();
+    entries.into_iter().map(|(_, entry)| entry).collect()
+}
+
+/// Reads and parses a single value from the file at `path`.
+fn parse_file<T>(path: &Path) -> Result<T, ReadoutError>
+where
+    T: std::str::FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let text = shared::read_file(path)?;
+    let text = text.trim();
+
+    text.parse::<T>().map_err(|e| {
+        ReadoutError::from_source(ReadoutErrorKind::ParseError, e)
+            .with_message(format!("Could not parse the value '{text}'"))
+            .with_path(path)
+    })
+}
+
+impl LinuxBatteryReadout {
+    /// Creates a battery readout that resolves its paths against `root`.
+    pub fn with_root<P: Into<PathBuf>>(root: P) -> Self {
+        LinuxBatteryReadout { root: root.into() }
     }
+}
 
-    fn percentage(&self) -> Result<u8, ReadoutError> {
-        if let Some(entries) = get_entries(Path::new("/sys/class/power_supply")) {
-            let dirs: Vec<PathBuf> = entries
-                .into_iter()
-                .filter(|x| {
-                    x.components()
-                        .last()
-                        .unwrap()
-                        .as_os_str()
-                        .to_string_lossy()
-                        .starts_with("BAT")
-                })
-                .collect();
-
-            if let Some(battery) = dirs.first() {
-                let path_to_capacity = battery.join("capacity");
-                let percentage_text = extra::pop_newline(fs::read_to_string(path_to_capacity)?);
-                let percentage_parsed = percentage_text.parse::<u8>();
-
-                match percentage_parsed {
-                    Ok(p) => return Ok(p),
-                    Err(e) => {
-                        return Err(ReadoutError::Other(format!(
-                            "Could not parse the value '{percentage_text}' into a digit: {e:?}"
-                        )))
-                    }
-                };
+impl LinuxKernelReadout {
+    /// Creates a kernel readout that resolves its paths against `root`.
+    pub fn with_root<P: Into<PathBuf>>(root: P) -> Self {
+        LinuxKernelReadout { root: root.into() }
+    }
+}
+
+impl LinuxGeneralReadout {
+    /// Creates a general readout that resolves its paths against `root`.
+    pub fn with_root<P: Into<PathBuf>>(root: P) -> Self {
+        LinuxGeneralReadout {
+            root: root.into(),
+            sysinfo: sysinfo::new(),
+        }
+    }
+}
+
+impl LinuxMemoryReadout {
+    /// Creates a memory readout that resolves its paths against `root`.
+    pub fn with_root<P: Into<PathBuf>>(root: P) -> Self {
+        LinuxMemoryReadout { root: root.into() }
+    }
+}
+
+impl LinuxProductReadout {
+    /// Creates a product readout that resolves its paths against `root`.
+    pub fn with_root<P: Into<PathBuf>>(root: P) -> Self {
+        LinuxProductReadout { root: root.into() }
+    }
+}
+
+impl LinuxPackageReadout {
+    /// Creates a package readout that resolves its paths against `root`.
+    pub fn with_root<P: Into<PathBuf>>(root: P) -> Self {
+        LinuxPackageReadout { root: root.into() }
+    }
+}
+
+impl LinuxNetworkReadout {
+    /// Creates a network readout that resolves its paths against `root`.
+    pub fn with_root<P: Into<PathBuf>>(root: P) -> Self {
+        LinuxNetworkReadout { root: root.into() }
+    }
+
+    /// Returns the directory of `interface` under `sys/class/net`, failing if no interface was
+    /// given.
+    fn interface_dir(
+        &self,
+        interface: Option<&str>,
+        metric: &str,
+    ) -> Result<PathBuf, ReadoutError> {
+        let ifname = interface.ok_or_else(|| {
+            ReadoutError::failed(ReadoutErrorKind::Other)
+                .with_metric(metric)
+                .with_message("Please specify a network interface to query.")
+        })?;
+
+        Ok(self.root.join("sys/class/net").join(ifname))
+    }
+
+    /// Reads the counter `metric` of `interface` from its `statistics` directory.
+    fn statistic(&self, interface: Option<&str>, metric: &str) -> Result<usize, ReadoutError> {
+        let path = self
+            .interface_dir(interface, metric)?
+            .join("statistics")
+            .join(metric);
+
+        parse_file::<usize>(&path).map_err(|e| e.with_metric(metric))
+    }
+}
+
+impl LinuxBatteryReadout {
+    /// Orders power supplies by name, comparing a trailing number by its value so that `BAT10`
+    /// comes after `BAT2`.
+    fn sort_supplies(entries: &mut [PathBuf]) {
+        entries.sort_by_cached_key(|dir| {
+            let name = dir.file_name().unwrap_or_default().to_string_lossy();
+            let prefix = name.trim_end_matches(|c: char| c.is_ascii_digit());
+            let number: Option<u64> = name[prefix.len()..].parse().ok();
+            (prefix.to_owned(), number)
+        });
+    }
+
+    /// Returns the directories of every system battery under `sys/class/power_supply`,
+    /// ordered by name. Batteries of peripherals, such as a wireless mouse, are left out.
+    fn battery_dirs(&self) -> Vec<PathBuf> {
+        let Some(mut entries) = get_entries(&self.root.join("sys/class/power_supply")) else {
+            return Vec::new();
+        };
+
+        Self::sort_supplies(&mut entries);
+        entries.retain(|dir| {
+            if read_rooted(dir, "scope").is_ok_and(|scope| scope == "Device") {
+                return false;
+            }
+
+            match read_rooted(dir, "type") {
+                Ok(kind) => kind == "Battery",
+                Err(_) => dir
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with("BAT")),
             }
+        });
+
+        entries
+    }
+
+    /// Returns the directories of every peripheral battery under `sys/class/power_supply`,
+    /// ordered by name.
+    fn peripheral_dirs(&self) -> Vec<PathBuf> {
+        let Some(mut entries) = get_entries(&self.root.join("sys/class/power_supply")) else {
+            return Vec::new();
+        };
+
+        Self::sort_supplies(&mut entries);
+        entries.retain(|dir| read_rooted(dir, "scope").is_ok_and(|scope| scope == "Device"));
+        entries
+    }
+
+    /// Guesses the kind of device from the name of its battery, which reflects the driver,
+    /// or from its model name.
+    fn peripheral_kind(name: &str, model: Option<&str>) -> PeripheralKind {
+        let name = name.to_lowercase();
+        let model = model.unwrap_or_default().to_lowercase();
+
+        if name.contains("controller")
+            || name.starts_with("xpadneo")
+            || model.contains("controller")
+        {
+            PeripheralKind::Controller
+        } else if name.starts_with("wacom") {
+            PeripheralKind::Tablet
+        } else if model.contains("mouse") {
+            PeripheralKind::Mouse
+        } else if model.contains("keyboard") {
+            PeripheralKind::Keyboard
+        } else if model.contains("headset") || model.contains("headphone") {
+            PeripheralKind::Headset
+        } else {
+            PeripheralKind::Unknown
+        }
+    }
+
+    fn describe_peripheral(battery: &Path) -> PeripheralBattery {
+        let name = battery
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let model = read_rooted(battery, "model_name").ok();
+
+        let capacity_level = read_rooted(battery, "capacity_level")
+            .ok()
+            .map(|level| match &level.to_lowercase()[..] {
+                "critical" => CapacityLevel::Critical,
+                "low" => CapacityLevel::Low,
+                "normal" => CapacityLevel::Normal,
+                "high" => CapacityLevel::High,
+                "full" => CapacityLevel::Full,
+                _ => CapacityLevel::Unknown,
+            });
+
+        PeripheralBattery {
+            kind: Self::peripheral_kind(&name, model.as_deref()),
+            name,
+            manufacturer: read_rooted(battery, "manufacturer").ok(),
+            model,
+            percentage: Self::read_percentage(battery).ok(),
+            capacity_level,
+            state: Self::read_status(battery).ok(),
+        }
+    }
+
+    /// Returns the directories of every external power source under `sys/class/power_supply`,
+    /// _e.g._ an AC adapter or a USB charger.
+    fn adapter_dirs(&self) -> Vec<PathBuf> {
+        let Some(mut entries) = get_entries(&self.root.join("sys/class/power_supply")) else {
+            return Vec::new();
+        };
+
+        Self::sort_supplies(&mut entries);
+        entries.retain(|dir| {
+            read_rooted(dir, "type").is_ok_and(|kind| kind == "Mains" || kind.starts_with("USB"))
+        });
+
+        entries
+    }
+
+    fn read_percentage(battery: &Path) -> Result<u8, ReadoutError> {
+        parse_file::<u8>(&battery.join("capacity")).map_err(|e| e.with_metric("percentage"))
+    }
+
+    fn read_status(battery: &Path) -> Result<BatteryState, ReadoutError> {
+        let path_to_status = battery.join("status");
+        let status_text = extra::pop_newline(
+            shared::read_file(&path_to_status).map_err(|e| e.with_metric("status"))?,
+        )
+        .to_lowercase();
+
+        match &status_text[..] {
+            "charging" => Ok(BatteryState::Charging),
+            "discharging" => Ok(BatteryState::Discharging),
+            "full" => Ok(BatteryState::Full),
+            "not charging" => Ok(BatteryState::NotCharging),
+            "unknown" => Ok(BatteryState::Unknown),
+            s => Err(ReadoutError::failed(ReadoutErrorKind::ParseError)
+                .with_metric("status")
+                .with_message(format!(
+                    "Got an unexpected value \"{s}\" reading battery status"
+                ))
+                .with_path(path_to_status)),
+        }
+    }
+
+    /// Reads the current and design capacity of `battery`, which is reported either as energy
+    /// (`energy_full`, in µWh) or as charge (`charge_full`, in µAh).
+    fn read_capacity(battery: &Path) -> Result<BatteryCapacity, ReadoutError> {
+        let (unit, prefix) = if battery.join("energy_full").exists() {
+            (CapacityUnit::WattHours, "energy")
+        } else if battery.join("charge_full").exists() {
+            (CapacityUnit::AmpereHours, "charge")
+        } else {
+            return Err(ReadoutError::MetricNotAvailable);
+        };
+
+        let read = |name: String| {
+            parse_file::<u64>(&battery.join(name))
+                .map(|micro| micro as f64 / 1e6)
+                .map_err(|e| e.with_metric("capacity"))
+        };
+
+        Ok(BatteryCapacity {
+            unit,
+            full: read(format!("{prefix}_full"))?,
+            full_design: read(format!("{prefix}_full_design"))?,
+            cycle_count: parse_file::<u64>(&battery.join("cycle_count"))
+                .ok()
+                .filter(|&count| count > 0),
+        })
+    }
+
+    fn read_health(battery: &Path) -> Result<u8, ReadoutError> {
+        Self::read_capacity(battery)
+            .map_err(|e| e.with_metric("health"))?
+            .health()
+            .ok_or(ReadoutError::MetricNotAvailable)
+    }
+
+    /// Reads a signed sysfs value given in millionths of a unit, e.g. µW. Some drivers report
+    /// negative values while discharging, which are returned as their magnitude.
+    fn read_micro(battery: &Path, name: &str) -> Result<f64, ReadoutError> {
+        parse_file::<i64>(&battery.join(name)).map(|micro| micro.unsigned_abs() as f64 / 1e6)
+    }
+
+    fn read_voltage(battery: &Path) -> Result<f64, ReadoutError> {
+        Self::read_micro(battery, "voltage_now").map_err(|e| e.with_metric("voltage"))
+    }
+
+    /// Reads the power drawn from or fed to `battery`, from `power_now`, or from `current_now`
+    /// and `voltage_now` if the battery reports its charge.
+    fn read_power(battery: &Path) -> Result<f64, ReadoutError> {
+        if battery.join("power_now").exists() {
+            return Self::read_micro(battery, "power_now").map_err(|e| e.with_metric("power"));
+        }
+
+        let current =
+            Self::read_micro(battery, "current_now").map_err(|e| e.with_metric("power"))?;
+        Ok(current * Self::read_voltage(battery).map_err(|e| e.with_metric("power"))?)
+    }
+
+    /// Estimates how long it takes until `battery` is empty or, if `to_full` is set, full. The
+    /// remaining energy is divided by the power, or the remaining charge by the current.
+    fn read_time(battery: &Path, to_full: bool) -> Result<Duration, ReadoutError> {
+        let (metric, expected) = if to_full {
+            ("time_to_full", BatteryState::Charging)
+        } else {
+            ("time_to_empty", BatteryState::Discharging)
         };
 
-        Err(ReadoutError::Other("No batteries detected.".to_string()))
+        if Self::read_status(battery)? != expected {
+            return Err(ReadoutError::MetricNotAvailable);
+        }
+
+        let (prefix, rate) = if battery.join("energy_now").exists() {
+            ("energy", Self::read_power(battery))
+        } else {
+            ("charge", Self::read_micro(battery, "current_now"))
+        };
+
+        let read = |name: &str| Self::read_micro(battery, &format!("{prefix}_{name}"));
+        let remaining = if to_full {
+            (read("full")? - read("now")?).max(0.0)
+        } else {
+            read("now")?
+        };
+
+        let rate = rate.map_err(|e| e.with_metric(metric))?;
+        if rate <= 0.0 {
+            return Err(ReadoutError::MetricNotAvailable);
+        }
+
+        Ok(Duration::from_secs_f64(remaining / rate * 3600.0))
+    }
+
+    /// Returns the energy `battery` holds when fully charged, in watt-hours. Batteries that
+    /// only report their charge are converted using their design voltage.
+    fn read_energy_full(battery: &Path) -> Option<f64> {
+        if let Ok(energy) = parse_file::<u64>(&battery.join("energy_full")) {
+            return Some(energy as f64 / 1e6);
+        }
+
+        let charge = parse_file::<u64>(&battery.join("charge_full")).ok()?;
+        let voltage = parse_file::<u64>(&battery.join("voltage_min_design")).ok()?;
+        Some(charge as f64 * voltage as f64 / 1e12)
+    }
+
+    fn describe(battery: &Path) -> Battery {
+        let name = battery
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Battery {
+            name,
+            percentage: Self::read_percentage(battery).ok(),
+            state: Self::read_status(battery).ok(),
+            health: Self::read_health(battery).ok(),
+            technology: read_rooted(battery, "technology").ok(),
+            manufacturer: read_rooted(battery, "manufacturer").ok(),
+            model: read_rooted(battery, "model_name").ok(),
+            energy_full: Self::read_energy_full(battery),
+            capacity: Self::read_capacity(battery).ok(),
+            power: Self::read_power(battery).ok(),
+            voltage: Self::read_voltage(battery).ok(),
+            time_to_empty: Self::read_time(battery, false).ok(),
+            time_to_full: Self::read_time(battery, true).ok(),
+        }
+    }
+}
+
+impl LinuxSensorReadout {
+    /// Creates a sensor readout that resolves its paths against `root`.
+    pub fn with_root<P: Into<PathBuf>>(root: P) -> Self {
+        LinuxSensorReadout { root: root.into() }
+    }
+}
+
+impl BatteryReadout for LinuxBatteryReadout {
+    fn new() -> Self {
+        LinuxBatteryReadout::with_root(DEFAULT_ROOT)
+    }
+
+    fn percentage(&self) -> Result<u8, ReadoutError> {
+        match self.battery_dirs().first() {
+            Some(battery) => Self::read_percentage(battery),
+            None => Err(ReadoutError::MetricNotAvailable),
+        }
     }
 
     fn status(&self) -> Result<BatteryState, ReadoutError> {
-        if let Some(entries) = get_entries(Path::new("/sys/class/power_supply")) {
-            let dirs: Vec<PathBuf> = entries
-                .into_iter()
-                .filter(|x| {
-                    x.components()
-                        .last()
-                        .unwrap()
-                        .as_os_str()
-                        .to_string_lossy()
-                        .starts_with("BAT")
-                })
-                .collect();
-
-            if let Some(battery) = dirs.first() {
-                let path_to_status = battery.join("status");
-                let status_text =
-                    extra::pop_newline(fs::read_to_string(path_to_status)?).to_lowercase();
-
-                match &status_text[..] {
-                    "charging" => return Ok(BatteryState::Charging),
-                    "discharging" | "full" => return Ok(BatteryState::Discharging),
-                    s => {
-                        return Err(ReadoutError::Other(format!(
-                            "Got an unexpected value \"{s}\" reading battery status"
-                        )))
-                    }
-                }
+        match self.battery_dirs().first() {
+            Some(battery) => Self::read_status(battery),
+            None => Err(ReadoutError::MetricNotAvailable),
+        }
+    }
+
+    fn ac_online(&self) -> Result<bool, ReadoutError> {
+        let adapters = self.adapter_dirs();
+        if adapters.is_empty() {
+            return Err(ReadoutError::MetricNotAvailable);
+        }
+
+        // Adapters whose state can't be read are skipped, unless none of them can be read.
+        let mut error = None;
+        let mut online = None;
+
+        for adapter in adapters {
+            match parse_file::<u8>(&adapter.join("online")) {
+                Ok(1) => return Ok(true),
+                Ok(_) => online = Some(false),
+                Err(e) => error = error.or(Some(e)),
             }
         }
 
-        Err(ReadoutError::Other("No batteries detected.".to_string()))
+        match (online, error) {
+            (Some(online), _) => Ok(online),
+            (None, Some(e)) => Err(e.with_metric("ac_online")),
+            (None, None) => Err(ReadoutError::MetricNotAvailable),
+        }
     }
 
     fn health(&self) -> Result<u8, ReadoutError> {
-        if let Some(entries) = get_entries(Path::new("/sys/class/power_supply")) {
-            let dirs: Vec<PathBuf> = entries
-                .into_iter()
-                .filter(|x| {
-                    !x.components()
-                        .last()
-                        .unwrap()
-                        .as_os_str()
-                        .to_string_lossy()
-                        .starts_with("ADP")
-                })
-                .collect();
-
-            if let Some(battery) = dirs.first() {
-                let energy_full =
-                    extra::pop_newline(fs::read_to_string(battery.join("energy_full"))?)
-                        .parse::<u8>();
-
-                let energy_full_design =
-                    extra::pop_newline(fs::read_to_string(battery.join("energy_full_design"))?)
-                        .parse::<u8>();
-
-                match (energy_full, energy_full_design) {
-                    (Ok(mut ef), Ok(efd)) => {
-                        if ef > efd {
-                            ef = efd;
-                            return Ok((ef as f32 / efd as f32 * 100_f32).ceil() as u8);
-                        }
+        match self.battery_dirs().first() {
+            Some(battery) => Self::read_health(battery),
+            None => Err(ReadoutError::MetricNotAvailable),
+        }
+    }
 
-                        return Ok((ef as f32 / efd as f32 * 100_f32).ceil() as u8);
-                    }
-                    _ => {
-                        return Err(ReadoutError::Other(
-                            "Error calculating battery health.".to_string(),
-                        ))
-                    }
-                }
-            }
+    fn capacity(&self) -> Result<BatteryCapacity, ReadoutError> {
+        match self.battery_dirs().first() {
+            Some(battery) => Self::read_capacity(battery),
+            None => Err(ReadoutError::MetricNotAvailable),
+        }
+    }
+
+    fn power(&self) -> Result<f64, ReadoutError> {
+        match self.battery_dirs().first() {
+            Some(battery) => Self::read_power(battery),
+            None => Err(ReadoutError::MetricNotAvailable),
+        }
+    }
+
+    fn voltage(&self) -> Result<f64, ReadoutError> {
+        match self.battery_dirs().first() {
+            Some(battery) => Self::read_voltage(battery),
+            None => Err(ReadoutError::MetricNotAvailable),
+        }
+    }
+
+    fn time_to_empty(&self) -> Result<Duration, ReadoutError> {
+        match self.battery_dirs().first() {
+            Some(battery) => Self::read_time(battery, false),
+            None => Err(ReadoutError::MetricNotAvailable),
         }
+    }
 
-        Err(ReadoutError::Other("No batteries detected.".to_string()))
+    fn time_to_full(&self) -> Result<Duration, ReadoutError> {
+        match self.battery_dirs().first() {
+            Some(battery) => Self::read_time(battery, true),
+            None => Err(ReadoutError::MetricNotAvailable),
+        }
+    }
+
+    fn peripherals(&self) -> Result<Vec<PeripheralBattery>, ReadoutError> {
+        let dirs = self.peripheral_dirs();
+        if dirs.is_empty() {
+            return Err(ReadoutError::MetricNotAvailable);
+        }
+
+        Ok(dirs
+            .iter()
+            .map(|dir| Self::describe_peripheral(dir))
+            .collect())
+    }
+
+    fn batteries(&self) -> Result<Vec<Battery>, ReadoutError> {
+        let dirs = self.battery_dirs();
+        if dirs.is_empty() {
+            return Err(ReadoutError::MetricNotAvailable);
+        }
+
+        Ok(dirs.iter().map(|dir| Self::describe(dir)).collect())
     }
 }
 
 impl KernelReadout for LinuxKernelReadout {
     fn new() -> Self {
-        LinuxKernelReadout {
-            os_release_ctl: Ctl::new("kernel.osrelease").ok(),
-            os_type_ctl: Ctl::new("kernel.ostype").ok(),
-        }
+        LinuxKernelReadout::with_root(DEFAULT_ROOT)
     }
 
     fn os_release(&self) -> Result<String, ReadoutError> {
-        Ok(self
-            .os_release_ctl
-            .as_ref()
-            .ok_or(ReadoutError::MetricNotAvailable)?
-            .value_string()?)
+        read_rooted(&self.root, "proc/sys/kernel/osrelease")
     }
 
     fn os_type(&self) -> Result<String, ReadoutError> {
-        Ok(self
-            .os_type_ctl
-            .as_ref()
-            .ok_or(ReadoutError::MetricNotAvailable)?
-            .value_string()?)
+        read_rooted(&self.root, "proc/sys/kernel/ostype")
     }
 }
 
 impl NetworkReadout for LinuxNetworkReadout {
     fn new() -> Self {
-        LinuxNetworkReadout
+        LinuxNetworkReadout::with_root(DEFAULT_ROOT)
     }
 
     fn tx_bytes(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
-        if let Some(ifname) = interface {
-            let rx_file = PathBuf::from("/sys/class/net")
-                .join(ifname)
-                .join("statistics/tx_bytes");
-            let content = std::fs::read_to_string(rx_file)?;
-            let bytes = extra::pop_newline(content)
-                .parse::<usize>()
-                .unwrap_or_default();
-            Ok(bytes)
-        } else {
-            Err(ReadoutError::Other(String::from(
-                "Please specify a network interface to query.",
-            )))
-        }
+        self.statistic(interface, "tx_bytes")
     }
 
     fn tx_packets(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
-        if let Some(ifname) = interface {
-            let rx_file = PathBuf::from("/sys/class/net")
-                .join(ifname)
-                .join("statistics/tx_packets");
-            let content = std::fs::read_to_string(rx_file)?;
-            let packets = extra::pop_newline(content)
-                .parse::<usize>()
-                .unwrap_or_default();
-            Ok(packets)
-        } else {
-            Err(ReadoutError::Other(String::from(
-                "Please specify a network interface to query.",
-            )))
-        }
+        self.statistic(interface, "tx_packets")
     }
 
     fn rx_bytes(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
-        if let Some(ifname) = interface {
-            let rx_file = PathBuf::from("/sys/class/net")
-                .join(ifname)
-                .join("statistics/rx_bytes");
-            let content = std::fs::read_to_string(rx_file)?;
-            let bytes = extra::pop_newline(content)
-                .parse::<usize>()
-                .unwrap_or_default();
-            Ok(bytes)
-        } else {
-            Err(ReadoutError::Other(String::from(
-                "Please specify a network interface to query.",
-            )))
-        }
+        self.statistic(interface, "rx_bytes")
     }
 
     fn rx_packets(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
-        if let Some(ifname) = interface {
-            let rx_file = PathBuf::from("/sys/class/net")
-                .join(ifname)
-                .join("statistics/rx_packets");
-            let content = std::fs::read_to_string(rx_file)?;
-            let packets = extra::pop_newline(content)
-                .parse::<usize>()
-                .unwrap_or_default();
-            Ok(packets)
-        } else {
-            Err(ReadoutError::Other(String::from(
-                "Please specify a network interface to query.",
-            )))
-        }
+        self.statistic(interface, "rx_packets")
     }
 
     fn physical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
-        if let Some(ifname) = interface {
-            let rx_file = PathBuf::from("/sys/class/net").join(ifname).join("address");
-            let content = std::fs::read_to_string(rx_file)?;
-            Ok(content)
-        } else {
-            Err(ReadoutError::Other(String::from(
-                "Please specify a network interface to query.",
-            )))
-        }
+        let path = self
+            .interface_dir(interface, "physical_address")?
+            .join("address");
+
+        shared::read_file(&path).map_err(|e| e.with_metric("physical_address"))
     }
 
     fn logical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
@@ -280,51 +628,38 @@ impl NetworkReadout for LinuxNetworkReadout {
 
 impl GeneralReadout for LinuxGeneralReadout {
     fn new() -> Self {
-        LinuxGeneralReadout {
-            hostname_ctl: Ctl::new("kernel.hostname").ok(),
-            sysinfo: sysinfo::new(),
-        }
+        LinuxGeneralReadout::with_root(DEFAULT_ROOT)
     }
 
     fn backlight(&self) -> Result<usize, ReadoutError> {
-        if let Some(base) = get_entries(Path::new("/sys/class/backlight/")) {
-            if let Some(backlight_path) = base.into_iter().next() {
-                let max_brightness_path = backlight_path.join("max_brightness");
-                let current_brightness_path = backlight_path.join("brightness");
-
-                let max_brightness_value =
-                    extra::pop_newline(fs::read_to_string(max_brightness_path)?)
-                        .parse::<usize>()
-                        .ok();
-
-                let current_brightness_value =
-                    extra::pop_newline(fs::read_to_string(current_brightness_path)?)
-                        .parse::<usize>()
-                        .ok();
-
-                match (current_brightness_value, max_brightness_value) {
-                    (Some(c), Some(m)) => {
-                        let brightness = c as f64 / m as f64 * 100f64;
-                        return Ok(brightness.round() as usize);
-                    }
-                    _ => {
-                        return Err(ReadoutError::Other(String::from(
-                            "Error occurred while calculating backlight (brightness) value.",
-                        )));
-                    }
-                }
-            }
+        let base = self.root.join("sys/class/backlight");
+        let Some(backlight_path) = get_entries(&base).and_then(|e| e.into_iter().next()) else {
+            return Err(ReadoutError::failed(ReadoutErrorKind::NotFound)
+                .with_metric("backlight")
+                .with_path(base)
+                .with_message("Could not obtain backlight information."));
+        };
+
+        let max_brightness_path = backlight_path.join("max_brightness");
+        let read = |path: &Path| parse_file::<usize>(path).map_err(|e| e.with_metric("backlight"));
+        let current_brightness = read(&backlight_path.join("brightness"))?;
+        let max_brightness = read(&max_brightness_path)?;
+
+        if max_brightness == 0 {
+            return Err(ReadoutError::failed(ReadoutErrorKind::ParseError)
+                .with_metric("backlight")
+                .with_path(max_brightness_path)
+                .with_message("The maximum brightness is 0."));
         }
 
-        Err(ReadoutError::Other(String::from(
-            "Could not obtain backlight information.",
-        )))
+        let brightness = current_brightness as f64 / max_brightness as f64 * 100f64;
+        Ok(brightness.round() as usize)
     }
 
     fn resolution(&self) -> Result<String, ReadoutError> {
-        let drm = Path::new("/sys/class/drm");
+        let drm = self.root.join("sys/class/drm");
 
-        if let Some(entries) = get_entries(drm) {
+        if let Some(entries) = get_entries(&drm) {
             let mut resolutions: Vec<String> = Vec::new();
             entries.into_iter().for_each(|entry| {
                 // Append "modes" to /sys/class/drm/<card>/
@@ -340,9 +675,10 @@ impl GeneralReadout for LinuxGeneralReadout {
             return Ok(resolutions.join(", "));
         }
 
-        Err(ReadoutError::Other(
-            "Could not obtain screen resolution from /sys/class/drm".to_string(),
-        ))
+        Err(ReadoutError::failed(ReadoutErrorKind::NotFound)
+            .with_metric("resolution")
+            .with_path(drm)
+            .with_message("Could not obtain screen resolution."))
     }
 
     fn username(&self) -> Result<String, ReadoutError> {
@@ -350,16 +686,12 @@ impl GeneralReadout for LinuxGeneralReadout {
     }
 
     fn hostname(&self) -> Result<String, ReadoutError> {
-        Ok(self
-            .hostname_ctl
-            .as_ref()
-            .ok_or(ReadoutError::MetricNotAvailable)?
-            .value_string()?)
+        read_rooted(&self.root, "proc/sys/kernel/hostname")
     }
 
     fn distribution(&self) -> Result<String, ReadoutError> {
         use os_release::OsRelease;
-        let content = OsRelease::new()?;
+        let content = OsRelease::new_from(self.root.join("etc/os-release"))?;
 
         if !content.version.is_empty() {
             return Ok(format!("{} {}", content.name, content.version));
@@ -454,7 +786,7 @@ impl GeneralReadout for LinuxGeneralReadout {
     }
 
     fn cpu_model_name(&self) -> Result<String, ReadoutError> {
-        Ok(shared::cpu_model_name())
+        shared::cpu_model_name_at(&self.root)
     }
 
     fn cpu_usage(&self) -> Result<usize, ReadoutError> {
@@ -475,28 +807,74 @@ impl GeneralReadout for LinuxGeneralReadout {
         ))
     }
 
+    fn cpu_stats(&self, interval: Duration) -> Result<CpuStats, ReadoutError> {
+        shared::cpu_stats_at(&self.root, interval)
+    }
+
+    fn load_average(&self) -> Result<LoadAverage, ReadoutError> {
+        shared::load_average_at(&self.root)
+    }
+
+    fn cpu_frequency(&self) -> Result<CpuFrequency, ReadoutError> {
+        cpu::cpu_frequency(&self.root)
+    }
+
+    fn cpu_topology(&self) -> Result<CpuTopology, ReadoutError> {
+        cpu::cpu_topology(&self.root)
+    }
+
+    fn cpu_features(&self) -> Result<CpuFeatures, ReadoutError> {
+        cpu::cpu_features(&self.root)
+    }
+
+    fn cpu_vulnerabilities(&self) -> Result<Vec<CpuVulnerability>, ReadoutError> {
+        cpu::cpu_vulnerabilities(&self.root)
+    }
+
+    fn cpu_limits(&self) -> Result<CpuLimits, ReadoutError> {
+        cgroup::cpu_limits(&self.root)
+    }
+
     fn cpu_physical_cores(&self) -> Result<usize, ReadoutError> {
-        use std::io::{BufRead, BufReader};
-        if let Ok(content) = File::open("/proc/cpuinfo") {
-            let reader = BufReader::new(content);
-            for line in reader.lines().map_while(Result::ok) {
-                if line.to_lowercase().starts_with("cpu cores") {
-                    return Ok(line
-                        .split(':')
-                        .nth(1)
-                        .unwrap()
-                        .trim()
-                        .parse::<usize>()
-                        .unwrap());
+        if let Ok(topology) = self.cpu_topology() {
+            return Ok(topology.cores);
+        }
+
+        // "cpu cores" is the number of cores per socket, so it's summed over the sockets.
+        let content = shared::read_file(self.root.join("proc/cpuinfo"))?;
+        let mut package = None;
+        let mut packages = Vec::new();
+
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+
+            match key.trim() {
+                "physical id" => package = value.trim().parse::<usize>().ok(),
+                "cpu cores" => {
+                    if let Ok(cores) = value.trim().parse::<usize>() {
+                        if !packages.iter().any(|(p, _)| *p == package) {
+                            packages.push((package, cores));
+                        }
+                    }
                 }
+                _ => {}
             }
         }
 
-        Err(ReadoutError::MetricNotAvailable)
+        match packages.iter().map(|(_, cores)| cores).sum() {
+            0 => Err(ReadoutError::MetricNotAvailable),
+            cores => Ok(cores),
+        }
     }
 
     fn cpu_cores(&self) -> Result<usize, ReadoutError> {
-        Ok(unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) } as usize)
+        if let Some(online) = cpu::read_cpu_list(&self.root, "sys/devices/system/cpu/online") {
+            return Ok(online.len());
+        }
+
+        Ok(unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) } as usize)
     }
 
     fn uptime(&self) -> Result<usize, ReadoutError> {
@@ -514,12 +892,12 @@ impl GeneralReadout for LinuxGeneralReadout {
     }
 
     fn machine(&self) -> Result<String, ReadoutError> {
-        let product_readout = LinuxProductReadout::new();
+        let product_readout = LinuxProductReadout::with_root(&self.root);
 
         let vendor = product_readout.vendor()?;
         let family = product_readout.family()?;
         let product = product_readout.product()?;
-        let version = extra::pop_newline(fs::read_to_string("/sys/class/dmi/id/product_version")?);
+        let version = read_rooted(&self.root, "sys/class/dmi/id/product_version")?;
 
         // If one field is generic, the others are likely the same, so fail the readout.
         if vendor.eq_ignore_ascii_case("system manufacturer") {
@@ -549,12 +927,18 @@ impl GeneralReadout for LinuxGeneralReadout {
     }
 
     fn gpus(&self) -> Result<Vec<String>, ReadoutError> {
-        let db = match Database::read() {
-            Ok(db) => db,
+        // Prefer a pci.ids database shipped inside the root, if any.
+        let db = ["usr/share/hwdata/pci.ids", "usr/share/misc/pci.ids"]
+            .iter()
+            .find_map(|p| Database::read_from_file(self.root.join(p)).ok())
+            .or_else(|| Database::read().ok());
+
+        let db = match db {
+            Some(db) => db,
             _ => return Err(ReadoutError::MetricNotAvailable),
         };
 
-        let devices = get_pci_devices()?;
+        let devices = get_pci_devices(&self.root)?;
         let mut gpus = vec![];
 
         for device in devices {
@@ -577,139 +961,97 @@ impl GeneralReadout for LinuxGeneralReadout {
 
 impl MemoryReadout for LinuxMemoryReadout {
     fn new() -> Self {
-        LinuxMemoryReadout {
-            sysinfo: sysinfo::new(),
-        }
+        LinuxMemoryReadout::with_root(DEFAULT_ROOT)
     }
 
     fn total(&self) -> Result<u64, ReadoutError> {
-        let mut info = self.sysinfo;
-        let info_ptr: *mut sysinfo = &mut info;
-        let ret = unsafe { sysinfo(info_ptr) };
-        if ret != -1 {
-            Ok(info.totalram as u64 * info.mem_unit as u64 / 1024)
-        } else {
-            Err(ReadoutError::Other(
-                "Something went wrong during the initialization of the sysinfo struct.".to_string(),
-            ))
-        }
+        Ok(self.breakdown()?.total)
     }
 
     fn free(&self) -> Result<u64, ReadoutError> {
-        let mut info = self.sysinfo;
-        let info_ptr: *mut sysinfo = &mut info;
-        let ret = unsafe { sysinfo(info_ptr) };
-        if ret != -1 {
-            Ok(info.freeram as u64 * info.mem_unit as u64 / 1024)
-        } else {
-            Err(ReadoutError::Other(
-                "Something went wrong during the initialization of the sysinfo struct.".to_string(),
-            ))
-        }
+        Ok(self.breakdown()?.free)
     }
 
     fn buffers(&self) -> Result<u64, ReadoutError> {
-        let mut info = self.sysinfo;
-        let info_ptr: *mut sysinfo = &mut info;
-        let ret = unsafe { sysinfo(info_ptr) };
-        if ret != -1 {
-            Ok(info.bufferram as u64 * info.mem_unit as u64 / 1024)
-        } else {
-            Err(ReadoutError::Other(
-                "Something went wrong during the initialization of the sysinfo struct.".to_string(),
-            ))
-        }
+        Ok(self.breakdown()?.buffers)
     }
 
     fn cached(&self) -> Result<u64, ReadoutError> {
-        Ok(shared::get_meminfo_value("Cached"))
+        Ok(self.breakdown()?.cached)
     }
 
     fn reclaimable(&self) -> Result<u64, ReadoutError> {
-        Ok(shared::get_meminfo_value("SReclaimable"))
+        self.breakdown()?
+            .slab_reclaimable
+            .ok_or(ReadoutError::MetricNotAvailable)
     }
 
     fn used(&self) -> Result<u64, ReadoutError> {
-        let total = self.total().unwrap();
-        match shared::get_meminfo_value("MemAvailable") {
-            0 => {
-                let free = self.free().unwrap();
-                let cached = self.cached().unwrap();
-                let reclaimable = self.reclaimable().unwrap();
-                let buffers = self.buffers().unwrap();
-                Ok(total - free - cached - reclaimable - buffers)
-            }
-            available => Ok(total - available),
-        }
+        Ok(self.breakdown()?.used())
     }
 
     fn swap_total(&self) -> Result<u64, ReadoutError> {
-        let mut info = self.sysinfo;
-        let info_ptr: *mut sysinfo = &mut info;
-        let ret = unsafe { sysinfo(info_ptr) };
-        if ret != -1 {
-            Ok(info.totalswap as u64 * info.mem_unit as u64 / 1024)
-        } else {
-            Err(ReadoutError::Other(
-                "Something went wrong during the initialization of the sysinfo struct.".to_string(),
-            ))
-        }
+        Ok(self.breakdown()?.swap_total)
     }
 
     fn swap_free(&self) -> Result<u64, ReadoutError> {
-        let mut info = self.sysinfo;
-        let info_ptr: *mut sysinfo = &mut info;
-        let ret = unsafe { sysinfo(info_ptr) };
-        if ret != -1 {
-            Ok(info.freeswap as u64 * info.mem_unit as u64 / 1024)
-        } else {
-            Err(ReadoutError::Other(
-                "Something went wrong during the initialization of the sysinfo struct.".to_string(),
-            ))
-        }
+        Ok(self.breakdown()?.swap_free)
     }
 
     fn swap_used(&self) -> Result<u64, ReadoutError> {
-        let mut info = self.sysinfo;
-        let info_ptr: *mut sysinfo = &mut info;
-        let ret = unsafe { sysinfo(info_ptr) };
-        if ret != -1 {
-            Ok((info.totalswap as u64 - info.freeswap as u64) * info.mem_unit as u64 / 1024)
-        } else {
-            Err(ReadoutError::Other(
-                "Something went wrong during the initialization of the sysinfo struct.".to_string(),
-            ))
-        }
+        Ok(self.breakdown()?.swap_used())
+    }
+
+    fn breakdown(&self) -> Result<MemoryBreakdown, ReadoutError> {
+        shared::read_meminfo_at(&self.root)
+    }
+
+    fn pressure(&self) -> Result<SystemPressure, ReadoutError> {
+        memory::pressure(&self.root)
+    }
+
+    fn vm_stats(&self) -> Result<VmStats, ReadoutError> {
+        memory::vm_stats(&self.root)
+    }
+
+    fn swap(&self) -> Result<SwapInfo, ReadoutError> {
+        memory::swap(&self.root)
+    }
+
+    fn limits(&self) -> Result<MemoryLimits, ReadoutError> {
+        cgroup::memory_limits(&self.root)
+    }
+
+    fn numa_nodes(&self) -> Result<Vec<NumaNode>, ReadoutError> {
+        memory::numa_nodes(&self.root)
+    }
+
+    fn huge_pages(&self) -> Result<HugePages, ReadoutError> {
+        memory::huge_pages(&self.root)
     }
 }
 
 impl ProductReadout for LinuxProductReadout {
     fn new() -> Self {
-        LinuxProductReadout
+        LinuxProductReadout::with_root(DEFAULT_ROOT)
     }
 
     fn vendor(&self) -> Result<String, ReadoutError> {
-        Ok(extra::pop_newline(fs::read_to_string(
-            "/sys/class/dmi/id/sys_vendor",
-        )?))
+        read_rooted(&self.root, "sys/class/dmi/id/sys_vendor")
     }
 
     fn family(&self) -> Result<String, ReadoutError> {
-        Ok(extra::pop_newline(fs::read_to_string(
-            "/sys/class/dmi/id/product_family",
-        )?))
+        read_rooted(&self.root, "sys/class/dmi/id/product_family")
     }
 
     fn product(&self) -> Result<String, ReadoutError> {
-        Ok(extra::pop_newline(fs::read_to_string(
-            "/sys/class/dmi/id/product_name",
-        )?))
+        read_rooted(&self.root, "sys/class/dmi/id/product_name")
     }
 }
 
 impl PackageReadout for LinuxPackageReadout {
     fn new() -> Self {
-        LinuxPackageReadout
+        LinuxPackageReadout::with_root(DEFAULT_ROOT)
     }
 
     fn count_pkgs(&self) -> Vec<(PackageManager, usize)> {
@@ -719,54 +1061,54 @@ impl PackageReadout for LinuxPackageReadout {
         // Acquire the value of HOME early on to avoid
         // doing it multiple times.
         if let Ok(path) = std::env::var("HOME") {
-            home = PathBuf::from(path);
+            home = self.rooted(path);
         }
 
-        if let Some(c) = LinuxPackageReadout::count_pacman() {
+        if let Some(c) = self.count_pacman() {
             packages.push((PackageManager::Pacman, c));
         }
 
-        if let Some(c) = LinuxPackageReadout::count_dpkg() {
+        if let Some(c) = self.count_dpkg() {
             packages.push((PackageManager::Dpkg, c));
         }
 
-        if let Some(c) = LinuxPackageReadout::count_rpm() {
+        if let Some(c) = self.count_rpm() {
             packages.push((PackageManager::Rpm, c));
         }
 
-        if let Some(c) = LinuxPackageReadout::count_portage() {
+        if let Some(c) = self.count_portage() {
             packages.push((PackageManager::Portage, c));
         }
 
-        if let Some(c) = LinuxPackageReadout::count_cargo() {
+        if let Some(c) = self.count_cargo() {
             packages.push((PackageManager::Cargo, c));
         }
 
-        if let Some(c) = LinuxPackageReadout::count_xbps() {
+        if let Some(c) = self.count_xbps() {
             packages.push((PackageManager::Xbps, c));
         }
 
-        if let Some(c) = LinuxPackageReadout::count_eopkg() {
+        if let Some(c) = self.count_eopkg() {
             packages.push((PackageManager::Eopkg, c));
         }
 
-        if let Some(c) = LinuxPackageReadout::count_apk() {
+        if let Some(c) = self.count_apk() {
             packages.push((PackageManager::Apk, c));
         }
 
-        if let Some(c) = LinuxPackageReadout::count_flatpak(&home) {
+        if let Some(c) = self.count_flatpak(&home) {
             packages.push((PackageManager::Flatpak, c));
         }
 
-        if let Some(c) = LinuxPackageReadout::count_snap() {
+        if let Some(c) = self.count_snap() {
             packages.push((PackageManager::Snap, c));
         }
 
-        if let Some(c) = LinuxPackageReadout::count_homebrew(&home) {
+        if let Some(c) = self.count_homebrew(&home) {
             packages.push((PackageManager::Homebrew, c));
         }
 
-        if let Some(c) = LinuxPackageReadout::count_nix() {
+        if let Some(c) = self.count_nix() {
             packages.push((PackageManager::Nix, c));
         }
 
@@ -775,14 +1117,27 @@ impl PackageReadout for LinuxPackageReadout {
 }
 
 impl LinuxPackageReadout {
+    /// Resolves an absolute path, e.g. one taken from `HOME`, against the
+    /// configured root.
+    fn rooted<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        let path = path.as_ref();
+        self.root.join(path.strip_prefix("/").unwrap_or(path))
+    }
+
+    /// Returns `true` if the readout inspects the running system rather than
+    /// an alternate root.
+    fn is_host_root(&self) -> bool {
+        self.root == Path::new(DEFAULT_ROOT)
+    }
+
     /// Returns the number of installed packages for systems
     /// that utilize `rpm` as their package manager.
-    fn count_rpm() -> Option<usize> {
+    fn count_rpm(&self) -> Option<usize> {
         // Return the number of installed packages using sqlite (~1ms)
         // as directly calling rpm or dnf is too expensive (~500ms)
         let count_sqlite = 'sqlite: {
-            let db = "/var/lib/rpm/rpmdb.sqlite";
-            if !Path::new(db).is_file() {
+            let db = self.root.join("var/lib/rpm/rpmdb.sqlite");
+            if !db.is_file() {
                 break 'sqlite None;
             }
 
@@ -802,14 +1157,20 @@ impl LinuxPackageReadout {
             None
         };
 
+        // librpm always opens the database of the running system, so
+        // it can only serve as a fallback for the host root.
+        if !self.is_host_root() {
+            return count_sqlite;
+        }
+
         // If counting with sqlite failed, try using librpm instead
         count_sqlite.or_else(|| unsafe { rpm_pkg_count::count() }.map(|count| count as usize))
     }
 
     /// Returns the number of installed packages for systems
     /// that utilize `pacman` as their package manager.
-    fn count_pacman() -> Option<usize> {
-        let pacman_dir = Path::new("/var/lib/pacman/local");
+    fn count_pacman(&self) -> Option<usize> {
+        let pacman_dir = self.root.join("var/lib/pacman/local");
         if pacman_dir.is_dir() {
             if let Ok(read_dir) = read_dir(pacman_dir) {
                 return Some(read_dir.count() - 1); // Ignore ALPM_DB_VERSION
@@ -821,8 +1182,8 @@ impl LinuxPackageReadout {
 
     /// Returns the number of installed packages for systems
     /// that utilize `eopkg` as their package manager.
-    fn count_eopkg() -> Option<usize> {
-        let eopkg_dir = Path::new("/var/lib/eopkg/package");
+    fn count_eopkg(&self) -> Option<usize> {
+        let eopkg_dir = self.root.join("var/lib/eopkg/package");
         if eopkg_dir.is_dir() {
             if let Ok(read_dir) = read_dir(eopkg_dir) {
                 return Some(read_dir.count());
@@ -834,8 +1195,8 @@ impl LinuxPackageReadout {
 
     /// Returns the number of installed packages for systems
     /// that utilize `portage` as their package manager.
-    fn count_portage() -> Option<usize> {
-        let pkg_dir = Path::new("/var/db/pkg");
+    fn count_portage(&self) -> Option<usize> {
+        let pkg_dir = self.root.join("var/db/pkg");
         if pkg_dir.exists() {
             return Some(
                 walkdir::WalkDir::new(pkg_dir)
@@ -851,10 +1212,10 @@ impl LinuxPackageReadout {
 
     /// Returns the number of installed packages for systems
     /// that utilize `dpkg` as their package manager.
-    fn count_dpkg() -> Option<usize> {
-        let dpkg_dir = Path::new("/var/lib/dpkg/info");
+    fn count_dpkg(&self) -> Option<usize> {
+        let dpkg_dir = self.root.join("var/lib/dpkg/info");
 
-        get_entries(dpkg_dir).map(|entries| {
+        get_entries(&dpkg_dir).map(|entries| {
             entries
                 .iter()
                 .filter(|x| extra::path_extension(x).unwrap_or_default() == "list")
@@ -864,12 +1225,12 @@ impl LinuxPackageReadout {
 
     /// Returns the number of installed packages for systems
     /// that have `homebrew` installed.
-    fn count_homebrew(home: &Path) -> Option<usize> {
+    fn count_homebrew(&self, home: &Path) -> Option<usize> {
         let keepme = OsStr::new(".keepme");
         let mut base = home.join(".linuxbrew");
 
         if !base.is_dir() {
-            base = PathBuf::from("/home/linuxbrew/.linuxbrew");
+            base = self.root.join("home/linuxbrew/.linuxbrew");
         }
 
         if !base.is_dir() {
@@ -890,28 +1251,35 @@ impl LinuxPackageReadout {
 
     /// Returns the number of installed packages for systems
     /// that utilize `xbps` as their package manager.
-    fn count_xbps() -> Option<usize> {
-        if !extra::which("xbps-query") {
-            return None;
+    fn count_xbps(&self) -> Option<usize> {
+        if command::allowed() && extra::which("xbps-query") {
+            let root = self.root.as_os_str();
+            let xbps_output =
+                command::run("xbps-query", [OsStr::new("-r"), root, OsStr::new("-l")]);
+
+            if let Ok(output) = xbps_output {
+                return extra::count_lines(output);
+            }
         }
 
-        let xbps_output = Command::new("xbps-query")
-            .arg("-l")
-            .stdout(Stdio::piped())
-            .output()
-            .unwrap();
-
-        extra::count_lines(
-            String::from_utf8(xbps_output.stdout)
-                .expect("ERROR: \"xbps-query -l\" output was not valid UTF-8"),
-        )
+        // fallback for when processes may not be spawned or xbps-query fails: every
+        // package in the package database has exactly one "pkgver" key
+        let pkgdb = get_entries(&self.root.join("var/db/xbps"))?
+            .into_iter()
+            .find(|e| {
+                let name = e.file_name().unwrap_or_default().to_string_lossy();
+                name.starts_with("pkgdb-") && name.ends_with(".plist")
+            })?;
+
+        let content = fs::read_to_string(pkgdb).ok()?;
+        Some(content.matches("<key>pkgver</key>").count())
     }
 
     /// Returns the number of installed packages for systems
     /// that utilize `apk` as their package manager.
-    fn count_apk() -> Option<usize> {
+    fn count_apk(&self) -> Option<usize> {
         // faster method for alpine: count empty lines in /lib/apk/db/installed
-        if let Ok(content) = fs::read_to_string(Path::new("/lib/apk/db/installed")) {
+        if let Ok(content) = fs::read_to_string(self.root.join("lib/apk/db/installed")) {
             return Some(content.lines().filter(|l| l.is_empty()).count());
         }
 
@@ -920,31 +1288,27 @@ impl LinuxPackageReadout {
             return None;
         }
 
-        let apk_output = Command::new("apk")
-            .arg("info")
-            .stdout(Stdio::piped())
-            .output()
-            .unwrap();
+        let root = self.root.as_os_str();
+        let apk_output = command::run("apk", [OsStr::new("--root"), root, OsStr::new("info")]);
 
-        extra::count_lines(
-            String::from_utf8(apk_output.stdout)
-                .expect("ERROR: \"apk info\" output was not valid UTF-8"),
-        )
+        extra::count_lines(apk_output.ok()?)
     }
 
     /// Returns the number of installed packages for systems
-    /// that have `cargo` installed.
-    fn count_cargo() -> Option<usize> {
-        shared::count_cargo()
+    /// that have `cargo` installed, looking up `$CARGO_HOME`, or
+    /// `$HOME/.cargo`, under the configured root.
+    fn count_cargo(&self) -> Option<usize> {
+        let cargo_home = home::cargo_home().ok()?;
+        shared::count_cargo_in(&self.rooted(cargo_home))
     }
 
     /// Returns the number of installed packages for systems
     /// that have `flatpak` installed.
-    fn count_flatpak(home: &Path) -> Option<usize> {
+    fn count_flatpak(&self, home: &Path) -> Option<usize> {
         let mut total: usize = 0;
         let filter = Regex::new(r".*\.(Locale|Debug)").unwrap();
         for install in [
-            Path::new("/var/lib/flatpak"),
+            &self.root.join("var/lib/flatpak"),
             &home.join(".local/share/flatpak"),
         ] {
             if install.exists() {
@@ -976,9 +1340,9 @@ impl LinuxPackageReadout {
 
     /// Returns the number of installed packages for systems
     /// that have `snap` installed.
-    fn count_snap() -> Option<usize> {
-        let snap_dir = Path::new("/var/lib/snapd/snaps");
-        if let Some(entries) = get_entries(snap_dir) {
+    fn count_snap(&self) -> Option<usize> {
+        let snap_dir = self.root.join("var/lib/snapd/snaps");
+        if let Some(entries) = get_entries(&snap_dir) {
             return Some(
                 entries
                     .iter()
@@ -992,34 +1356,393 @@ impl LinuxPackageReadout {
 
     /// Returns the number of installed packages for systems
     /// that utilize `nix` as their package manager.
-    fn count_nix() -> Option<usize> {
-        return 'sqlite: {
-            let db = "/nix/var/nix/db/db.sqlite";
-            if !Path::new(db).is_file() {
-                break 'sqlite None;
+    fn count_nix(&self) -> Option<usize> {
+        let db = self.root.join("nix/var/nix/db/db.sqlite");
+        if !db.is_file() {
+            return None;
+        }
+
+        let connection = sqlite::Connection::open_with_flags(
+            // The nix store is immutable, so we need to inform sqlite about it
+            format!("file:{}?immutable=1", db.display()),
+            sqlite::OpenFlags::new().with_read_only().with_uri(),
+        );
+
+        if let Ok(con) = connection {
+            let statement =
+                con.prepare("SELECT COUNT(path) FROM ValidPaths WHERE sigs IS NOT NULL");
+
+            if let Ok(mut s) = statement {
+                if s.next().is_ok() {
+                    return match s.read::<Option<i64>, _>(0) {
+                        Ok(Some(count)) => Some(count as usize),
+                        _ => None,
+                    };
+                }
             }
+        }
 
-            let connection = sqlite::Connection::open_with_flags(
-                // The nix store is immutable, so we need to inform sqlite about it
-                "file:".to_owned() + db + "?immutable=1",
-                sqlite::OpenFlags::new().with_read_only().with_uri(),
-            );
+        None
+    }
+}
 
-            if let Ok(con) = connection {
-                let statement =
-                    con.prepare("SELECT COUNT(path) FROM ValidPaths WHERE sigs IS NOT NULL");
+impl SensorReadout for LinuxSensorReadout {
+    fn new() -> Self {
+        LinuxSensorReadout::with_root(DEFAULT_ROOT)
+    }
 
-                if let Ok(mut s) = statement {
-                    if s.next().is_ok() {
-                        break 'sqlite match s.read::<Option<i64>, _>(0) {
-                            Ok(Some(count)) => Some(count as usize),
-                            _ => None,
-                        };
-                    }
-                }
-            }
+    fn chips(&self) -> Result<Vec<SensorChip>, ReadoutError> {
+        let mut chips = hwmon::read_hwmon_chips(&self.root);
+        let zones = hwmon::read_thermal_zones(&self.root, &chips);
+        chips.extend(zones);
 
-            None
-        };
+        if chips.is_empty() {
+            return Err(ReadoutError::MetricNotAvailable);
+        }
+
+        Ok(chips)
+    }
+
+    fn cpu_temperature(&self) -> Result<f64, ReadoutError> {
+        hwmon::cpu_temperature(&self.chips()?).ok_or(ReadoutError::MetricNotAvailable)
+    }
+}
+
+/// The metrics implemented by this backend.
+pub(crate) const CAPABILITIES: &[Capability] = &[
+    Capability::new(ReadoutKind::Battery, "percentage"),
+    Capability::new(ReadoutKind::Battery, "status"),
+    Capability::new(ReadoutKind::Battery, "ac_online"),
+    Capability::new(ReadoutKind::Battery, "health"),
+    Capability::new(ReadoutKind::Battery, "capacity"),
+    Capability::new(ReadoutKind::Battery, "power"),
+    Capability::new(ReadoutKind::Battery, "voltage"),
+    Capability::new(ReadoutKind::Battery, "time_to_empty"),
+    Capability::new(ReadoutKind::Battery, "time_to_full"),
+    Capability::new(ReadoutKind::Battery, "batteries"),
+    Capability::new(ReadoutKind::Battery, "peripherals"),
+    Capability::new(ReadoutKind::Battery, "combined_percentage"),
+    Capability::new(ReadoutKind::Kernel, "os_release"),
+    Capability::new(ReadoutKind::Kernel, "os_type"),
+    Capability::new(ReadoutKind::Kernel, "pretty_kernel"),
+    Capability::new(ReadoutKind::Memory, "total"),
+    Capability::new(ReadoutKind::Memory, "free"),
+    Capability::new(ReadoutKind::Memory, "buffers"),
+    Capability::new(ReadoutKind::Memory, "cached"),
+    Capability::new(ReadoutKind::Memory, "reclaimable"),
+    Capability::new(ReadoutKind::Memory, "used"),
+    Capability::new(ReadoutKind::Memory, "swap_total"),
+    Capability::new(ReadoutKind::Memory, "swap_free"),
+    Capability::new(ReadoutKind::Memory, "swap_used"),
+    Capability::new(ReadoutKind::Memory, "breakdown"),
+    Capability::new(ReadoutKind::Memory, "pressure"),
+    Capability::new(ReadoutKind::Memory, "vm_stats"),
+    Capability::new(ReadoutKind::Memory, "swap"),
+    Capability::new(ReadoutKind::Memory, "limits"),
+    Capability::new(ReadoutKind::Memory, "numa_nodes"),
+    Capability::new(ReadoutKind::Memory, "huge_pages"),
+    Capability::new(ReadoutKind::General, "backlight"),
+    Capability::new(ReadoutKind::General, "resolution"),
+    Capability::new(ReadoutKind::General, "username"),
+    Capability::new(ReadoutKind::General, "hostname"),
+    Capability::new(ReadoutKind::General, "distribution"),
+    Capability::new(ReadoutKind::General, "desktop_environment"),
+    Capability::new(ReadoutKind::General, "session"),
+    Capability::new(ReadoutKind::General, "window_manager").requires(&[
+        Prerequisite::WaylandCompositor,
+        Prerequisite::Program("xprop"),
+        Prerequisite::Program("wmctrl"),
+    ]),
+    Capability::new(ReadoutKind::General, "terminal"),
+    Capability::new(ReadoutKind::General, "shell"),
+    Capability::new(ReadoutKind::General, "cpu_model_name"),
+    Capability::new(ReadoutKind::General, "cpu_usage"),
+    Capability::new(ReadoutKind::General, "cpu_stats"),
+    Capability::new(ReadoutKind::General, "load_average"),
+    Capability::new(ReadoutKind::General, "cpu_frequency"),
+    Capability::new(ReadoutKind::General, "cpu_topology"),
+    Capability::new(ReadoutKind::General, "cpu_features"),
+    Capability::new(ReadoutKind::General, "cpu_vulnerabilities"),
+    Capability::new(ReadoutKind::General, "cpu_limits"),
+    Capability::new(ReadoutKind::General, "cpu_physical_cores"),
+    Capability::new(ReadoutKind::General, "cpu_cores"),
+    Capability::new(ReadoutKind::General, "uptime"),
+    Capability::new(ReadoutKind::General, "machine"),
+    Capability::new(ReadoutKind::General, "disk_space"),
+    Capability::new(ReadoutKind::General, "gpus").requires(&[Prerequisite::File("pci.ids")]),
+    Capability::new(ReadoutKind::Product, "vendor"),
+    Capability::new(ReadoutKind::Product, "family"),
+    Capability::new(ReadoutKind::Product, "product"),
+    Capability::new(ReadoutKind::Package, "count_pkgs").requires(&[
+        Prerequisite::Library("librpm"),
+        Prerequisite::Program("xbps-query"),
+        Prerequisite::Program("apk"),
+    ]),
+    Capability::new(ReadoutKind::Network, "tx_bytes"),
+    Capability::new(ReadoutKind::Network, "tx_packets"),
+    Capability::new(ReadoutKind::Network, "rx_bytes"),
+    Capability::new(ReadoutKind::Network, "rx_packets"),
+    Capability::new(ReadoutKind::Network, "logical_address"),
+    Capability::new(ReadoutKind::Network, "physical_address"),
+    Capability::new(ReadoutKind::Sensor, "chips"),
+    Capability::new(ReadoutKind::Sensor, "cpu_temperature"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::fixture::Fixture;
+    use super::*;
+
+    #[test]
+    fn test_readouts_with_root() {
+        let root = Fixture::new(
+            "root",
+            &[
+                ("proc/sys/kernel/ostype", "Linux\n"),
+                ("proc/sys/kernel/osrelease", "6.1.0\n"),
+                ("proc/sys/kernel/hostname", "fixture\n"),
+                ("proc/loadavg", "0.52 0.58 0.59 2/1234 56789\n"),
+                ("sys/class/power_supply/BAT0/capacity", "42\n"),
+                ("sys/class/dmi/id/sys_vendor", "LENOVO\n"),
+                ("var/lib/pacman/local/ALPM_DB_VERSION", "9\n"),
+                ("var/lib/pacman/local/linux-6.1.0-1/desc", ""),
+            ],
+        );
+
+        let kernel = LinuxKernelReadout::with_root(root.path());
+        assert_eq!(kernel.pretty_kernel().unwrap(), "Linux 6.1.0");
+
+        let general = LinuxGeneralReadout::with_root(root.path());
+        assert_eq!(general.hostname().unwrap(), "fixture");
+
+        let load = general.load_average().unwrap();
+        assert_eq!((load.one, load.five, load.fifteen), (0.52, 0.58, 0.59));
+        assert_eq!(
+            (load.runnable_tasks, load.total_tasks),
+            (Some(2), Some(1234))
+        );
+
+        let battery = LinuxBatteryReadout::with_root(root.path());
+        assert_eq!(battery.percentage().unwrap(), 42);
+
+        let product = LinuxProductReadout::with_root(root.path());
+        assert_eq!(product.vendor().unwrap(), "LENOVO");
+
+        let packages = LinuxPackageReadout::with_root(root.path());
+        assert_eq!(packages.count_pacman(), Some(1));
+    }
+
+    #[test]
+    fn test_batteries() {
+        let root = Fixture::new(
+            "batteries",
+            &[
+                ("sys/class/power_supply/AC/type", "Mains\n"),
+                ("sys/class/power_supply/AC/online", "1\n"),
+                ("sys/class/power_supply/BAT1/type", "Battery\n"),
+                ("sys/class/power_supply/BAT1/status", "Not charging\n"),
+                ("sys/class/power_supply/BAT1/capacity", "100\n"),
+                ("sys/class/power_supply/BAT1/energy_full", "23000000\n"),
+                ("sys/class/power_supply/BAT0/type", "Battery\n"),
+                ("sys/class/power_supply/BAT0/status", "Discharging\n"),
+                ("sys/class/power_supply/BAT0/capacity", "50\n"),
+                ("sys/class/power_supply/BAT0/energy_full", "69000000\n"),
+                ("sys/class/power_supply/BAT0/technology", "Li-poly\n"),
+                ("sys/class/power_supply/BAT0/model_name", "01AV431\n"),
+                ("sys/class/power_supply/hidpp_battery_0/type", "Battery\n"),
+                ("sys/class/power_supply/hidpp_battery_0/scope", "Device\n"),
+                (
+                    "sys/class/power_supply/hidpp_battery_0/model_name",
+                    "Wireless Mouse M325\n",
+                ),
+                (
+                    "sys/class/power_supply/hidpp_battery_0/capacity_level",
+                    "High\n",
+                ),
+                (
+                    "sys/class/power_supply/hidpp_battery_0/status",
+                    "Discharging\n",
+                ),
+                (
+                    "sys/class/power_supply/ps-controller-battery-a0:5a:5d:00:00:00/scope",
+                    "Device\n",
+                ),
+                (
+                    "sys/class/power_supply/ps-controller-battery-a0:5a:5d:00:00:00/capacity",
+                    "80\n",
+                ),
+            ],
+        );
+
+        let battery = LinuxBatteryReadout::with_root(root.path());
+        let batteries = battery.batteries().unwrap();
+        assert_eq!(batteries.len(), 2);
+        assert_eq!(batteries[0].name, "BAT0");
+        assert_eq!(batteries[0].state, Some(BatteryState::Discharging));
+        assert_eq!(batteries[0].technology.as_deref(), Some("Li-poly"));
+        assert_eq!(batteries[0].model.as_deref(), Some("01AV431"));
+        assert_eq!(batteries[1].energy_full, Some(23.0));
+        assert_eq!(batteries[1].state, Some(BatteryState::NotCharging));
+        assert!(battery.ac_online().unwrap());
+
+        // An adapter whose state can't be read doesn't hide one that is online.
+        let usb = root.path().join("sys/class/power_supply/0-USB");
+        fs::create_dir_all(&usb).unwrap();
+        fs::write(usb.join("type"), "USB\n").unwrap();
+        assert!(battery.ac_online().unwrap());
+
+        let peripherals = battery.peripherals().unwrap();
+        assert_eq!(peripherals.len(), 2);
+        assert_eq!(peripherals[0].kind, PeripheralKind::Mouse);
+        assert_eq!(peripherals[0].percentage, None);
+        assert_eq!(peripherals[0].capacity_level, Some(CapacityLevel::High));
+        assert_eq!(peripherals[0].state, Some(BatteryState::Discharging));
+        assert_eq!(peripherals[1].kind, PeripheralKind::Controller);
+        assert_eq!(peripherals[1].percentage, Some(80));
+        assert_eq!(battery.percentage().unwrap(), 50);
+        assert_eq!(battery.combined_percentage().unwrap(), 63);
+
+        // Batteries are ordered by their number rather than by name.
+        for name in ["BAT10", "BAT2"] {
+            let dir = root.path().join("sys/class/power_supply").join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("type"), "Battery\n").unwrap();
+        }
+
+        let names: Vec<String> = battery
+            .batteries()
+            .unwrap()
+            .into_iter()
+            .map(|battery| battery.name)
+            .collect();
+        assert_eq!(names, ["BAT0", "BAT1", "BAT2", "BAT10"]);
+    }
+
+    #[test]
+    fn test_battery_capacity() {
+        let root = Fixture::new(
+            "capacity",
+            &[
+                // Most laptops report energy in µWh.
+                ("sys/class/power_supply/BAT0/energy_full", "45120000\n"),
+                (
+                    "sys/class/power_supply/BAT0/energy_full_design",
+                    "57020000\n",
+                ),
+                ("sys/class/power_supply/BAT0/cycle_count", "312\n"),
+                // Others, and many phones, only report charge in µAh.
+                ("sys/class/power_supply/BAT1/charge_full", "4800000\n"),
+                (
+                    "sys/class/power_supply/BAT1/charge_full_design",
+                    "4400000\n",
+                ),
+                ("sys/class/power_supply/BAT1/cycle_count", "0\n"),
+                // Some firmware doesn't know the design capacity.
+                ("sys/class/power_supply/BAT2/energy_full", "50000000\n"),
+                ("sys/class/power_supply/BAT2/energy_full_design", "0\n"),
+            ],
+        );
+
+        let battery = LinuxBatteryReadout::with_root(root.path());
+        let capacity = battery.capacity().unwrap();
+        assert_eq!(capacity.unit, CapacityUnit::WattHours);
+        assert_eq!((capacity.full, capacity.full_design), (45.12, 57.02));
+        assert_eq!(capacity.cycle_count, Some(312));
+        assert_eq!(battery.health().unwrap(), 79);
+
+        let batteries = battery.batteries().unwrap();
+        let capacity = batteries[1].capacity.unwrap();
+        assert_eq!(capacity.unit, CapacityUnit::AmpereHours);
+        assert_eq!((capacity.full, capacity.full_design), (4.8, 4.4));
+        assert_eq!(capacity.cycle_count, None);
+        assert_eq!(batteries[1].health, Some(100));
+
+        assert!(batteries[2].capacity.is_some());
+        assert_eq!(batteries[2].health, None);
+    }
+
+    #[test]
+    fn test_battery_power() {
+        let root = Fixture::new(
+            "power",
+            &[
+                ("sys/class/power_supply/BAT0/status", "Discharging\n"),
+                ("sys/class/power_supply/BAT0/power_now", "12400000\n"),
+                ("sys/class/power_supply/BAT0/voltage_now", "11800000\n"),
+                ("sys/class/power_supply/BAT0/energy_now", "27590000\n"),
+                ("sys/class/power_supply/BAT0/energy_full", "50000000\n"),
+                ("sys/class/power_supply/BAT1/status", "Charging\n"),
+                ("sys/class/power_supply/BAT1/current_now", "-2000000\n"),
+                ("sys/class/power_supply/BAT1/voltage_now", "4000000\n"),
+                ("sys/class/power_supply/BAT1/charge_now", "3000000\n"),
+                ("sys/class/power_supply/BAT1/charge_full", "4000000\n"),
+            ],
+        );
+
+        let battery = LinuxBatteryReadout::with_root(root.path());
+        assert_eq!(battery.power().unwrap(), 12.4);
+        assert_eq!(battery.voltage().unwrap(), 11.8);
+        assert_eq!(battery.time_to_empty().unwrap().as_secs(), 8010);
+        assert!(matches!(
+            battery.time_to_full(),
+            Err(ReadoutError::MetricNotAvailable)
+        ));
+
+        let batteries = battery.batteries().unwrap();
+        assert_eq!(batteries[1].power, Some(8.0));
+        assert_eq!(batteries[1].time_to_full, Some(Duration::from_secs(1800)));
+        assert_eq!(batteries[1].time_to_empty, None);
+    }
+
+    #[test]
+    fn test_structured_errors() {
+        let root = Fixture::new(
+            "errors",
+            &[
+                ("sys/class/power_supply/BAT0/capacity", "full\n"),
+                ("sys/class/net/eth0/statistics/rx_bytes", "lots\n"),
+            ],
+        );
+
+        let battery = LinuxBatteryReadout::with_root(root.path());
+        let error = battery.percentage().unwrap_err();
+        let failure = error.failure().unwrap();
+        assert_eq!(failure.kind, ReadoutErrorKind::ParseError);
+        assert_eq!(failure.metric.as_deref(), Some("percentage"));
+        assert_eq!(
+            failure.path.as_deref(),
+            Some(
+                root.path()
+                    .join("sys/class/power_supply/BAT0/capacity")
+                    .as_path()
+            )
+        );
+        assert!(std::error::Error::source(&error).is_some());
+
+        let error = battery.status().unwrap_err();
+        assert_eq!(error.kind(), Some(ReadoutErrorKind::NotFound));
+
+        let product = LinuxProductReadout::with_root(root.path());
+        assert_eq!(
+            product.vendor().unwrap_err().kind(),
+            Some(ReadoutErrorKind::NotFound)
+        );
+
+        let memory = LinuxMemoryReadout::with_root(root.path());
+        assert_eq!(
+            memory.used().unwrap_err().kind(),
+            Some(ReadoutErrorKind::NotFound)
+        );
+
+        let network = LinuxNetworkReadout::with_root(root.path());
+        let error = network.rx_bytes(Some("eth0")).unwrap_err();
+        let failure = error.failure().unwrap();
+        assert_eq!(failure.kind, ReadoutErrorKind::ParseError);
+        assert_eq!(failure.metric.as_deref(), Some("rx_bytes"));
+        assert!(failure.path.is_some());
+        assert_eq!(
+            network.tx_bytes(None).unwrap_err().kind(),
+            Some(ReadoutErrorKind::Other)
+        );
     }
 }
@@ -1,7 +1,7 @@
 use std::{
     fs::{read_dir, read_to_string},
     io,
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use pciid_parser::{schema::SubDeviceId, Database};
@@ -68,13 +68,9 @@ impl PciDevice {
         let device_value = self.read_value(PciDeviceReadableValues::Device);
         let sub_device_value = self.read_value(PciDeviceReadableValues::SubDevice);
 
-        let Some(vendor) = db.vendors.get(&vendor_value) else {
-            return None;
-        };
+        let vendor = db.vendors.get(&vendor_value)?;
+        let device = vendor.devices.get(&device_value)?;
 
-        let Some(device) = vendor.devices.get(&device_value) else {
-            return None;
-        };
         // To return device name if no valid subdevice name is found
         let device_name = device.name.to_owned();
 
@@ -96,8 +92,8 @@ impl PciDevice {
         }
     }
 }
-pub fn get_pci_devices() -> Result<Vec<PciDevice>, io::Error> {
-    let devices_dir = read_dir("/sys/bus/pci/devices/")?;
+pub fn get_pci_devices(root: &Path) -> Result<Vec<PciDevice>, io::Error> {
+    let devices_dir = read_dir(root.join("sys/bus/pci/devices"))?;
 
     let mut devices = vec![];
     for device_entry in devices_dir.map_while(Result::ok) {
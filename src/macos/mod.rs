@@ -1,4 +1,5 @@
 #![allow(clippy::unnecessary_cast)]
+use crate::capabilities::{Capability, Prerequisite, ReadoutKind};
 use crate::extra;
 use crate::macos::mach_ffi::{io_registry_entry_t, DisplayServicesGetBrightness, IOObjectRelease};
 use crate::macos::mach_ffi::{
@@ -21,6 +22,7 @@ use mach2::kern_return::KERN_SUCCESS;
 use std::ffi::CString;
 use std::fs::DirEntry;
 use std::path::Path;
+use std::time::Duration;
 use sysctl::{Ctl, Sysctl};
 
 mod mach_ffi;
@@ -55,11 +57,21 @@ struct MacOSIOPMPowerSource {
     battery_installed: Option<bool>,
     state_of_charge: Option<usize>,
     charging: Option<bool>,
+    fully_charged: Option<bool>,
+    external_connected: Option<bool>,
+    /// In millivolts.
+    voltage: Option<i64>,
+    /// In milliamperes, negative while discharging.
+    amperage: Option<i64>,
+    /// In minutes, `65535` while the estimate is unknown.
+    avg_time_to_empty: Option<i64>,
+    avg_time_to_full: Option<i64>,
 }
 
 pub struct MacOSPackageReadout;
 
 pub struct MacOSNetworkReadout;
+pub struct MacOSSensorReadout;
 
 impl BatteryReadout for MacOSBatteryReadout {
     fn new() -> Self {
@@ -84,6 +96,10 @@ impl BatteryReadout for MacOSBatteryReadout {
         if let Some(charging) = power_info.charging {
             return Ok(if charging {
                 BatteryState::Charging
+            } else if power_info.fully_charged == Some(true) {
+                BatteryState::Full
+            } else if power_info.external_connected == Some(true) {
+                BatteryState::NotCharging
             } else {
                 BatteryState::Discharging
             });
@@ -94,12 +110,63 @@ impl BatteryReadout for MacOSBatteryReadout {
         )))
     }
 
+    fn ac_online(&self) -> Result<bool, ReadoutError> {
+        let power_info = self.power_info.as_ref()?;
+
+        power_info.external_connected.ok_or(MetricNotAvailable)
+    }
+
     fn health(&self) -> Result<u8, ReadoutError> {
         Err(ReadoutError::NotImplemented)
     }
+
+    fn power(&self) -> Result<f64, ReadoutError> {
+        let power_info = self.power_info.as_ref()?;
+
+        match (power_info.amperage, power_info.voltage) {
+            (Some(amperage), Some(voltage)) => {
+                Ok(amperage.unsigned_abs() as f64 * voltage as f64 / 1e6)
+            }
+            _ => Err(MetricNotAvailable),
+        }
+    }
+
+    fn voltage(&self) -> Result<f64, ReadoutError> {
+        let power_info = self.power_info.as_ref()?;
+
+        Ok(power_info.voltage.ok_or(MetricNotAvailable)? as f64 / 1e3)
+    }
+
+    fn time_to_empty(&self) -> Result<Duration, ReadoutError> {
+        let power_info = self.power_info.as_ref()?;
+
+        if power_info.charging != Some(false) {
+            return Err(MetricNotAvailable);
+        }
+
+        MacOSIOPMPowerSource::estimate(power_info.avg_time_to_empty)
+    }
+
+    fn time_to_full(&self) -> Result<Duration, ReadoutError> {
+        let power_info = self.power_info.as_ref()?;
+
+        if power_info.charging != Some(true) {
+            return Err(MetricNotAvailable);
+        }
+
+        MacOSIOPMPowerSource::estimate(power_info.avg_time_to_full)
+    }
 }
 
 impl MacOSIOPMPowerSource {
+    /// Converts an estimate reported by IOKit in minutes, which is `65535` while unknown.
+    fn estimate(minutes: Option<i64>) -> Result<Duration, ReadoutError> {
+        match minutes {
+            Some(minutes @ 0..=65534) => Ok(Duration::from_secs(minutes as u64 * 60)),
+            _ => Err(MetricNotAvailable),
+        }
+    }
+
     fn new() -> Result<Self, ReadoutError> {
         let battery_data_key = CFString::new("BatteryData");
         let power_source_dict = MacOSIOPMPowerSource::get_power_source_dict()?;
@@ -135,6 +202,21 @@ impl MacOSIOPMPowerSource {
                 let number = CFNumber::wrap_under_get_rule((*charging) as CFNumberRef);
                 instance.charging = Some(number.to_i32() != Some(0));
             }
+
+            let find_i64 = |key: &str| {
+                power_source_dict
+                    .find(&CFString::new(key).to_void())
+                    .and_then(|value| {
+                        CFNumber::wrap_under_get_rule((*value) as CFNumberRef).to_i64()
+                    })
+            };
+
+            instance.fully_charged = find_i64("FullyCharged").map(|value| value != 0);
+            instance.external_connected = find_i64("ExternalConnected").map(|value| value != 0);
+            instance.voltage = find_i64("Voltage");
+            instance.amperage = find_i64("Amperage");
+            instance.avg_time_to_empty = find_i64("AvgTimeToEmpty");
+            instance.avg_time_to_full = find_i64("AvgTimeToFull");
         }
 
         Ok(instance)
@@ -358,6 +440,10 @@ impl GeneralReadout for MacOSGeneralReadout {
         shared::cpu_usage()
     }
 
+    fn load_average(&self) -> Result<LoadAverage, ReadoutError> {
+        shared::load_average()
+    }
+
     fn cpu_physical_cores(&self) -> Result<usize, ReadoutError> {
         shared::cpu_physical_cores()
     }
@@ -709,3 +795,49 @@ fn macos_version_to_name(version: &NSOperatingSystemVersion) -> &'static str {
         _ => "Unknown",
     }
 }
+
+impl SensorReadout for MacOSSensorReadout {
+    fn new() -> Self {
+        MacOSSensorReadout
+    }
+}
+
+/// The metrics implemented by this backend.
+pub(crate) const CAPABILITIES: &[Capability] = &[
+    Capability::new(ReadoutKind::Battery, "percentage"),
+    Capability::new(ReadoutKind::Battery, "status"),
+    Capability::new(ReadoutKind::Battery, "ac_online"),
+    Capability::new(ReadoutKind::Battery, "power"),
+    Capability::new(ReadoutKind::Battery, "voltage"),
+    Capability::new(ReadoutKind::Battery, "time_to_empty"),
+    Capability::new(ReadoutKind::Battery, "time_to_full"),
+    Capability::new(ReadoutKind::Kernel, "os_release"),
+    Capability::new(ReadoutKind::Kernel, "os_type"),
+    Capability::new(ReadoutKind::Kernel, "pretty_kernel"),
+    Capability::new(ReadoutKind::Memory, "total"),
+    Capability::new(ReadoutKind::Memory, "free"),
+    Capability::new(ReadoutKind::Memory, "reclaimable"),
+    Capability::new(ReadoutKind::Memory, "used"),
+    Capability::new(ReadoutKind::General, "backlight"),
+    Capability::new(ReadoutKind::General, "resolution"),
+    Capability::new(ReadoutKind::General, "username"),
+    Capability::new(ReadoutKind::General, "hostname"),
+    Capability::new(ReadoutKind::General, "distribution"),
+    Capability::new(ReadoutKind::General, "desktop_environment"),
+    Capability::new(ReadoutKind::General, "window_manager"),
+    Capability::new(ReadoutKind::General, "terminal"),
+    Capability::new(ReadoutKind::General, "shell"),
+    Capability::new(ReadoutKind::General, "cpu_model_name"),
+    Capability::new(ReadoutKind::General, "cpu_usage"),
+    Capability::new(ReadoutKind::General, "load_average"),
+    Capability::new(ReadoutKind::General, "cpu_physical_cores"),
+    Capability::new(ReadoutKind::General, "cpu_cores"),
+    Capability::new(ReadoutKind::General, "uptime"),
+    Capability::new(ReadoutKind::General, "machine"),
+    Capability::new(ReadoutKind::General, "os_name"),
+    Capability::new(ReadoutKind::General, "disk_space"),
+    Capability::new(ReadoutKind::Product, "vendor"),
+    Capability::new(ReadoutKind::Product, "product"),
+    Capability::new(ReadoutKind::Package, "count_pkgs").requires(&[Prerequisite::Program("brew")]),
+    Capability::new(ReadoutKind::Network, "logical_address"),
+];
@@ -0,0 +1,596 @@
+//! This module provides a static backend that implements every readout trait from a
+//! description of a system, rather than from the host it runs on.
+//!
+//! The description uses the same layout as a serialized [`SystemSnapshot`], so a snapshot
+//! taken on a real machine can be replayed as is. Each metric holds either `Ok` with a value
+//! or `Err` with the [`ReadoutError`] it should fail with; metrics that are left out fail with
+//! [`ReadoutError::NotImplemented`].
+//!
+//! ```toml
+//! [battery]
+//! percentage = { Ok = 87 }
+//! status = { Ok = "Discharging" }
+//! health = { Err = "MetricNotAvailable" }
+//!
+//! [general]
+//! hostname = { Ok = "thinkpad" }
+//! cpu_cores = { Ok = 8 }
+//! ```
+//!
+//! This backend is only available with the `mock` feature.
+
+use crate::snapshot::*;
+use crate::traits::*;
+use std::path::Path;
+use std::time::Duration;
+
+impl From<serde_json::Error> for ReadoutError {
+    fn from(e: serde_json::Error) -> Self {
+        ReadoutError::from_source(ReadoutErrorKind::ParseError, e)
+    }
+}
+
+impl From<toml::de::Error> for ReadoutError {
+    fn from(e: toml::de::Error) -> Self {
+        ReadoutError::from_source(ReadoutErrorKind::ParseError, e)
+    }
+}
+
+pub struct MockBatteryReadout {
+    snapshot: BatterySnapshot,
+}
+
+pub struct MockKernelReadout {
+    snapshot: KernelSnapshot,
+}
+
+pub struct MockMemoryReadout {
+    snapshot: MemorySnapshot,
+}
+
+pub struct MockGeneralReadout {
+    snapshot: GeneralSnapshot,
+}
+
+pub struct MockProductReadout {
+    snapshot: ProductSnapshot,
+}
+
+pub struct MockPackageReadout {
+    packages: Result<Vec<(PackageManager, usize)>, ReadoutError>,
+}
+
+pub struct MockNetworkReadout {
+    snapshot: NetworkSnapshot,
+}
+
+pub struct MockSensorReadout {
+    snapshot: SensorSnapshot,
+}
+
+/// The mock counterpart of [`Readouts`](crate::Readouts), holding one mock readout of each
+/// kind.
+pub struct MockReadouts {
+    pub battery: MockBatteryReadout,
+    pub kernel: MockKernelReadout,
+    pub memory: MockMemoryReadout,
+    pub general: MockGeneralReadout,
+    pub product: MockProductReadout,
+    pub packages: MockPackageReadout,
+    pub network: MockNetworkReadout,
+    pub sensors: MockSensorReadout,
+}
+
+impl MockReadouts {
+    /// Creates mock readouts that report the values of `snapshot`.
+    pub fn from_snapshot(snapshot: SystemSnapshot) -> Self {
+        MockReadouts {
+            battery: MockBatteryReadout::from_snapshot(snapshot.battery),
+            kernel: MockKernelReadout::from_snapshot(snapshot.kernel),
+            memory: MockMemoryReadout::from_snapshot(snapshot.memory),
+            general: MockGeneralReadout::from_snapshot(snapshot.general),
+            product: MockProductReadout::from_snapshot(snapshot.product),
+            packages: MockPackageReadout::from_snapshot(snapshot.packages),
+            network: MockNetworkReadout::from_snapshot(snapshot.network),
+            sensors: MockSensorReadout::from_snapshot(snapshot.sensors),
+        }
+    }
+
+    /// Creates mock readouts from a JSON description.
+    pub fn from_json(json: &str) -> Result<Self, ReadoutError> {
+        Ok(MockReadouts::from_snapshot(serde_json::from_str(json)?))
+    }
+
+    /// Creates mock readouts from a TOML description.
+    pub fn from_toml(toml: &str) -> Result<Self, ReadoutError> {
+        Ok(MockReadouts::from_snapshot(toml::from_str(toml)?))
+    }
+
+    /// Creates mock readouts from a description file. Files ending in `.toml` are parsed as
+    /// TOML, everything else as JSON.
+    pub fn from_path(path: &Path) -> Result<Self, ReadoutError> {
+        let content = std::fs::read_to_string(path)?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => MockReadouts::from_toml(&content),
+            _ => MockReadouts::from_json(&content),
+        }
+    }
+}
+
+impl MockBatteryReadout {
+    /// Creates a battery readout that reports the values of `snapshot`.
+    pub fn from_snapshot(snapshot: BatterySnapshot) -> Self {
+        MockBatteryReadout { snapshot }
+    }
+}
+
+impl MockKernelReadout {
+    /// Creates a kernel readout that reports the values of `snapshot`.
+    pub fn from_snapshot(snapshot: KernelSnapshot) -> Self {
+        MockKernelReadout { snapshot }
+    }
+}
+
+impl MockMemoryReadout {
+    /// Creates a memory readout that reports the values of `snapshot`.
+    pub fn from_snapshot(snapshot: MemorySnapshot) -> Self {
+        MockMemoryReadout { snapshot }
+    }
+}
+
+impl MockGeneralReadout {
+    /// Creates a general readout that reports the values of `snapshot`.
+    pub fn from_snapshot(snapshot: GeneralSnapshot) -> Self {
+        MockGeneralReadout { snapshot }
+    }
+}
+
+impl MockProductReadout {
+    /// Creates a product readout that reports the values of `snapshot`.
+    pub fn from_snapshot(snapshot: ProductSnapshot) -> Self {
+        MockProductReadout { snapshot }
+    }
+}
+
+impl MockPackageReadout {
+    /// Creates a package readout that reports `packages`, or no packages at all if they are an
+    /// error.
+    pub fn from_snapshot(packages: Result<Vec<(PackageManager, usize)>, ReadoutError>) -> Self {
+        MockPackageReadout { packages }
+    }
+}
+
+impl MockNetworkReadout {
+    /// Creates a network readout that reports the values of `snapshot`.
+    pub fn from_snapshot(snapshot: NetworkSnapshot) -> Self {
+        MockNetworkReadout { snapshot }
+    }
+
+    /// Returns `value` if `interface` is the one the snapshot describes, or if the snapshot
+    /// doesn't name one.
+    fn get<T: Clone>(
+        &self,
+        interface: Option<&str>,
+        value: &Result<T, ReadoutError>,
+    ) -> Result<T, ReadoutError> {
+        match (&self.snapshot.interface, interface) {
+            (Some(expected), Some(queried)) if expected != queried => {
+                Err(ReadoutError::MetricNotAvailable)
+            }
+            _ => value.clone(),
+        }
+    }
+}
+
+impl MockSensorReadout {
+    /// Creates a sensor readout that reports the values of `snapshot`.
+    pub fn from_snapshot(snapshot: SensorSnapshot) -> Self {
+        MockSensorReadout { snapshot }
+    }
+}
+
+impl BatteryReadout for MockBatteryReadout {
+    fn new() -> Self {
+        MockBatteryReadout::from_snapshot(BatterySnapshot::default())
+    }
+
+    fn percentage(&self) -> Result<u8, ReadoutError> {
+        self.snapshot.percentage.clone()
+    }
+
+    fn status(&self) -> Result<BatteryState, ReadoutError> {
+        self.snapshot.status.clone()
+    }
+
+    fn health(&self) -> Result<u8, ReadoutError> {
+        self.snapshot.health.clone()
+    }
+
+    fn batteries(&self) -> Result<Vec<Battery>, ReadoutError> {
+        self.snapshot.batteries.clone()
+    }
+
+    fn combined_percentage(&self) -> Result<u8, ReadoutError> {
+        self.snapshot.combined_percentage.clone()
+    }
+
+    fn capacity(&self) -> Result<BatteryCapacity, ReadoutError> {
+        self.snapshot.capacity.clone()
+    }
+
+    fn power(&self) -> Result<f64, ReadoutError> {
+        self.snapshot.power.clone()
+    }
+
+    fn voltage(&self) -> Result<f64, ReadoutError> {
+        self.snapshot.voltage.clone()
+    }
+
+    fn time_to_empty(&self) -> Result<Duration, ReadoutError> {
+        self.snapshot.time_to_empty.clone()
+    }
+
+    fn time_to_full(&self) -> Result<Duration, ReadoutError> {
+        self.snapshot.time_to_full.clone()
+    }
+
+    fn ac_online(&self) -> Result<bool, ReadoutError> {
+        self.snapshot.ac_online.clone()
+    }
+
+    fn peripherals(&self) -> Result<Vec<PeripheralBattery>, ReadoutError> {
+        self.snapshot.peripherals.clone()
+    }
+}
+
+impl KernelReadout for MockKernelReadout {
+    fn new() -> Self {
+        MockKernelReadout::from_snapshot(KernelSnapshot::default())
+    }
+
+    fn os_release(&self) -> Result<String, ReadoutError> {
+        self.snapshot.os_release.clone()
+    }
+
+    fn os_type(&self) -> Result<String, ReadoutError> {
+        self.snapshot.os_type.clone()
+    }
+
+    fn pretty_kernel(&self) -> Result<String, ReadoutError> {
+        self.snapshot.pretty_kernel.clone()
+    }
+}
+
+impl MemoryReadout for MockMemoryReadout {
+    fn new() -> Self {
+        MockMemoryReadout::from_snapshot(MemorySnapshot::default())
+    }
+
+    fn total(&self) -> Result<u64, ReadoutError> {
+        self.snapshot.total.clone()
+    }
+
+    fn free(&self) -> Result<u64, ReadoutError> {
+        self.snapshot.free.clone()
+    }
+
+    fn buffers(&self) -> Result<u64, ReadoutError> {
+        self.snapshot.buffers.clone()
+    }
+
+    fn cached(&self) -> Result<u64, ReadoutError> {
+        self.snapshot.cached.clone()
+    }
+
+    fn reclaimable(&self) -> Result<u64, ReadoutError> {
+        self.snapshot.reclaimable.clone()
+    }
+
+    fn used(&self) -> Result<u64, ReadoutError> {
+        self.snapshot.used.clone()
+    }
+
+    fn swap_total(&self) -> Result<u64, ReadoutError> {
+        self.snapshot.swap_total.clone()
+    }
+
+    fn swap_free(&self) -> Result<u64, ReadoutError> {
+        self.snapshot.swap_free.clone()
+    }
+
+    fn swap_used(&self) -> Result<u64, ReadoutError> {
+        self.snapshot.swap_used.clone()
+    }
+
+    fn breakdown(&self) -> Result<MemoryBreakdown, ReadoutError> {
+        self.snapshot.breakdown.clone()
+    }
+
+    fn pressure(&self) -> Result<SystemPressure, ReadoutError> {
+        self.snapshot.pressure.clone()
+    }
+
+    fn vm_stats(&self) -> Result<VmStats, ReadoutError> {
+        self.snapshot.vm_stats.clone()
+    }
+
+    fn swap(&self) -> Result<SwapInfo, ReadoutError> {
+        self.snapshot.swap.clone()
+    }
+
+    fn limits(&self) -> Result<MemoryLimits, ReadoutError> {
+        self.snapshot.limits.clone()
+    }
+
+    fn numa_nodes(&self) -> Result<Vec<NumaNode>, ReadoutError> {
+        self.snapshot.numa_nodes.clone()
+    }
+
+    fn huge_pages(&self) -> Result<HugePages, ReadoutError> {
+        self.snapshot.huge_pages.clone()
+    }
+}
+
+impl GeneralReadout for MockGeneralReadout {
+    fn new() -> Self {
+        MockGeneralReadout::from_snapshot(GeneralSnapshot::default())
+    }
+
+    fn backlight(&self) -> Result<usize, ReadoutError> {
+        self.snapshot.backlight.clone()
+    }
+
+    fn resolution(&self) -> Result<String, ReadoutError> {
+        self.snapshot.resolution.clone()
+    }
+
+    fn username(&self) -> Result<String, ReadoutError> {
+        self.snapshot.username.clone()
+    }
+
+    fn hostname(&self) -> Result<String, ReadoutError> {
+        self.snapshot.hostname.clone()
+    }
+
+    fn distribution(&self) -> Result<String, ReadoutError> {
+        self.snapshot.distribution.clone()
+    }
+
+    fn desktop_environment(&self) -> Result<String, ReadoutError> {
+        self.snapshot.desktop_environment.clone()
+    }
+
+    fn session(&self) -> Result<String, ReadoutError> {
+        self.snapshot.session.clone()
+    }
+
+    fn window_manager(&self) -> Result<String, ReadoutError> {
+        self.snapshot.window_manager.clone()
+    }
+
+    fn terminal(&self) -> Result<String, ReadoutError> {
+        self.snapshot.terminal.clone()
+    }
+
+    fn shell(&self, format: ShellFormat, kind: ShellKind) -> Result<String, ReadoutError> {
+        let shell = match kind {
+            ShellKind::Current => self.snapshot.shell.clone()?,
+            ShellKind::Default => self.snapshot.default_shell.clone()?,
+        };
+
+        match format {
+            ShellFormat::Relative => Ok(Path::new(&shell)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or(shell)),
+            ShellFormat::Absolute => Ok(shell),
+        }
+    }
+
+    fn cpu_model_name(&self) -> Result<String, ReadoutError> {
+        self.snapshot.cpu_model_name.clone()
+    }
+
+    fn cpu_usage(&self) -> Result<usize, ReadoutError> {
+        self.snapshot.cpu_usage.clone()
+    }
+
+    fn cpu_stats(&self, _interval: Duration) -> Result<CpuStats, ReadoutError> {
+        self.snapshot.cpu_stats.clone()
+    }
+
+    fn cpu_physical_cores(&self) -> Result<usize, ReadoutError> {
+        self.snapshot.cpu_physical_cores.clone()
+    }
+
+    fn cpu_cores(&self) -> Result<usize, ReadoutError> {
+        self.snapshot.cpu_cores.clone()
+    }
+
+    fn uptime(&self) -> Result<usize, ReadoutError> {
+        self.snapshot.uptime.clone()
+    }
+
+    fn machine(&self) -> Result<String, ReadoutError> {
+        self.snapshot.machine.clone()
+    }
+
+    fn os_name(&self) -> Result<String, ReadoutError> {
+        self.snapshot.os_name.clone()
+    }
+
+    fn disk_space(&self, _: &Path) -> Result<(u64, u64), ReadoutError> {
+        self.snapshot.disk_space.clone()
+    }
+
+    fn gpus(&self) -> Result<Vec<String>, ReadoutError> {
+        self.snapshot.gpus.clone()
+    }
+
+    fn load_average(&self) -> Result<LoadAverage, ReadoutError> {
+        self.snapshot.load_average.clone()
+    }
+
+    fn cpu_frequency(&self) -> Result<CpuFrequency, ReadoutError> {
+        self.snapshot.cpu_frequency.clone()
+    }
+
+    fn cpu_topology(&self) -> Result<CpuTopology, ReadoutError> {
+        self.snapshot.cpu_topology.clone()
+    }
+
+    fn cpu_features(&self) -> Result<CpuFeatures, ReadoutError> {
+        self.snapshot.cpu_features.clone()
+    }
+
+    fn cpu_vulnerabilities(&self) -> Result<Vec<CpuVulnerability>, ReadoutError> {
+        self.snapshot.cpu_vulnerabilities.clone()
+    }
+
+    fn cpu_limits(&self) -> Result<CpuLimits, ReadoutError> {
+        self.snapshot.cpu_limits.clone()
+    }
+}
+
+impl ProductReadout for MockProductReadout {
+    fn new() -> Self {
+        MockProductReadout::from_snapshot(ProductSnapshot::default())
+    }
+
+    fn vendor(&self) -> Result<String, ReadoutError> {
+        self.snapshot.vendor.clone()
+    }
+
+    fn family(&self) -> Result<String, ReadoutError> {
+        self.snapshot.family.clone()
+    }
+
+    fn product(&self) -> Result<String, ReadoutError> {
+        self.snapshot.product.clone()
+    }
+}
+
+impl PackageReadout for MockPackageReadout {
+    fn new() -> Self {
+        MockPackageReadout::from_snapshot(Ok(Vec::new()))
+    }
+
+    fn count_pkgs(&self) -> Vec<(PackageManager, usize)> {
+        self.packages.clone().unwrap_or_default()
+    }
+}
+
+impl NetworkReadout for MockNetworkReadout {
+    fn new() -> Self {
+        MockNetworkReadout::from_snapshot(NetworkSnapshot::default())
+    }
+
+    fn tx_bytes(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        self.get(interface, &self.snapshot.tx_bytes)
+    }
+
+    fn tx_packets(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        self.get(interface, &self.snapshot.tx_packets)
+    }
+
+    fn rx_bytes(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        self.get(interface, &self.snapshot.rx_bytes)
+    }
+
+    fn rx_packets(&self, interface: Option<&str>) -> Result<usize, ReadoutError> {
+        self.get(interface, &self.snapshot.rx_packets)
+    }
+
+    fn logical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        self.get(interface, &self.snapshot.logical_address)
+    }
+
+    fn physical_address(&self, interface: Option<&str>) -> Result<String, ReadoutError> {
+        self.get(interface, &self.snapshot.physical_address)
+    }
+}
+
+impl SensorReadout for MockSensorReadout {
+    fn new() -> Self {
+        MockSensorReadout::from_snapshot(SensorSnapshot::default())
+    }
+
+    fn chips(&self) -> Result<Vec<SensorChip>, ReadoutError> {
+        self.snapshot.chips.clone()
+    }
+
+    fn cpu_temperature(&self) -> Result<f64, ReadoutError> {
+        self.snapshot.cpu_temperature.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_toml() {
+        let mock = MockReadouts::from_toml(
+            r#"
+            packages = { Ok = [["Pacman", 1024], ["Flatpak", 12]] }
+
+            [battery]
+            percentage = { Ok = 87 }
+            status = { Ok = "Discharging" }
+            health = { Err = "MetricNotAvailable" }
+
+            [general]
+            hostname = { Ok = "thinkpad" }
+            shell = { Ok = "/usr/bin/zsh" }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(mock.battery.percentage().unwrap(), 87);
+        assert_eq!(mock.battery.status().unwrap(), BatteryState::Discharging);
+        assert!(matches!(
+            mock.battery.health(),
+            Err(ReadoutError::MetricNotAvailable)
+        ));
+        assert_eq!(mock.general.hostname().unwrap(), "thinkpad");
+        assert_eq!(
+            mock.general
+                .shell(ShellFormat::Relative, ShellKind::Current)
+                .unwrap(),
+            "zsh"
+        );
+        assert!(matches!(
+            mock.general.uptime(),
+            Err(ReadoutError::NotImplemented)
+        ));
+        assert_eq!(
+            mock.packages.count_pkgs()[0],
+            (PackageManager::Pacman, 1024)
+        );
+    }
+
+    #[test]
+    fn test_from_json() {
+        let mock = MockReadouts::from_json(
+            r#"{
+                "network": {
+                    "interface": "wlan0",
+                    "rx_bytes": { "Ok": 4096 },
+                    "logical_address": { "Err": { "Other": "No address assigned." } }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(mock.network.rx_bytes(Some("wlan0")).unwrap(), 4096);
+        assert!(matches!(
+            mock.network.rx_bytes(Some("eth0")),
+            Err(ReadoutError::MetricNotAvailable)
+        ));
+        assert!(matches!(
+            mock.network.logical_address(None),
+            Err(ReadoutError::Other(_))
+        ));
+    }
+}
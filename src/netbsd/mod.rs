@@ -1,4 +1,6 @@
 #![allow(clippy::unnecessary_cast)]
+use crate::capabilities::{Capability, Prerequisite, ReadoutKind};
+use crate::command;
 use crate::dirs;
 use crate::extra;
 use crate::shared;
@@ -10,7 +12,6 @@ use std::ffi::CString;
 use std::fs;
 use std::fs::read_dir;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
 
 pub struct NetBSDBatteryReadout;
 pub struct NetBSDKernelReadout;
@@ -19,6 +20,7 @@ pub struct NetBSDMemoryReadout;
 pub struct NetBSDProductReadout;
 pub struct NetBSDPackageReadout;
 pub struct NetBSDNetworkReadout;
+pub struct NetBSDSensorReadout;
 
 impl BatteryReadout for NetBSDBatteryReadout {
     fn new() -> Self {
@@ -27,14 +29,7 @@ impl BatteryReadout for NetBSDBatteryReadout {
 
     fn percentage(&self) -> Result<u8, ReadoutError> {
         if extra::which("envstat") {
-            let envstat = Command::new("envstat")
-                .args(["-s", "acpibat0:charge"])
-                .stdout(Stdio::piped())
-                .output()
-                .expect("ERROR: failed to spawn \"envstat\" process");
-
-            let envstat_out = String::from_utf8(envstat.stdout)
-                .expect("ERROR: \"envstat\" process stdout was not valid UTF-8");
+            let envstat_out = command::run("envstat", ["-s", "acpibat0:charge"])?;
             if envstat_out.is_empty() {
                 return Err(ReadoutError::MetricNotAvailable);
             } else {
@@ -47,7 +42,10 @@ impl BatteryReadout for NetBSDBatteryReadout {
                             .map_or("", |m| m.as_str())
                             .to_string()
                             .replace([' ', '%'], "");
-                        let percentage_f = percentage.parse::<f32>().unwrap();
+                        let percentage_f = percentage.parse::<f32>().map_err(|e| {
+                            ReadoutError::from_source(ReadoutErrorKind::ParseError, e)
+                                .with_command("envstat -s acpibat0:charge")
+                        })?;
                         let percentage_i = percentage_f.round() as u8;
                         return Ok(percentage_i);
                     }
@@ -61,14 +59,7 @@ impl BatteryReadout for NetBSDBatteryReadout {
 
     fn status(&self) -> Result<BatteryState, ReadoutError> {
         if extra::which("envstat") {
-            let envstat = Command::new("envstat")
-                .args(["-s", "acpibat0:charging"])
-                .stdout(Stdio::piped())
-                .output()
-                .expect("ERROR: failed to spawn \"envstat\" process");
-
-            let envstat_out = String::from_utf8(envstat.stdout)
-                .expect("ERROR: \"envstat\" process stdout was not valid UTF-8");
+            let envstat_out = command::run("envstat", ["-s", "acpibat0:charging"])?;
 
             if envstat_out.is_empty() {
                 return Err(ReadoutError::MetricNotAvailable);
@@ -93,25 +84,13 @@ impl KernelReadout for NetBSDKernelReadout {
     }
 
     fn os_release(&self) -> Result<String, ReadoutError> {
-        let output = Command::new("sysctl")
-            .args(["-n", "-b", "kern.osrelease"])
-            .output()
-            .expect("ERROR: failed to fetch \"kernel.osrelease\" using \"sysctl\"");
-
-        let osrelease = String::from_utf8(output.stdout)
-            .expect("ERROR: \"sysctl\" process stdout was not valid UTF-8");
+        let osrelease = command::run("sysctl", ["-n", "-b", "kern.osrelease"])?;
 
         Ok(osrelease)
     }
 
     fn os_type(&self) -> Result<String, ReadoutError> {
-        let output = Command::new("sysctl")
-            .args(["-n", "-b", "kern.ostype"])
-            .output()
-            .expect("ERROR: failed to fetch \"kernel.ostype\" using \"sysctl\"");
-
-        let osrelease = String::from_utf8(output.stdout)
-            .expect("ERROR: \"sysctl\" process stdout was not valid UTF-8");
+        let osrelease = command::run("sysctl", ["-n", "-b", "kern.ostype"])?;
 
         Ok(osrelease)
     }
@@ -133,13 +112,7 @@ impl GeneralReadout for NetBSDGeneralReadout {
     }
 
     fn backlight(&self) -> Result<usize, ReadoutError> {
-        let output = Command::new("sysctl")
-            .args(["-n", "hw.acpi.acpiout0.brightness"])
-            .output()
-            .expect("ERROR: failed to fetch \"hw.acpi.acpiout0.brightness\" using \"sysctl\"");
-
-        let backlight = String::from_utf8(output.stdout)
-            .expect("ERROR: \"sysctl\" process stdout was not valid UTF-8");
+        let backlight = command::run("sysctl", ["-n", "hw.acpi.acpiout0.brightness"])?;
 
         if backlight.is_empty() {
             return Err(ReadoutError::Other(String::from(
@@ -280,7 +253,7 @@ impl GeneralReadout for NetBSDGeneralReadout {
     }
 
     fn cpu_model_name(&self) -> Result<String, ReadoutError> {
-        Ok(shared::cpu_model_name())
+        shared::cpu_model_name()
     }
 
     fn cpu_cores(&self) -> Result<usize, ReadoutError> {
@@ -295,6 +268,10 @@ impl GeneralReadout for NetBSDGeneralReadout {
         shared::cpu_usage()
     }
 
+    fn load_average(&self) -> Result<LoadAverage, ReadoutError> {
+        shared::load_average()
+    }
+
     fn uptime(&self) -> Result<usize, ReadoutError> {
         shared::uptime()
     }
@@ -354,11 +331,11 @@ impl MemoryReadout for NetBSDMemoryReadout {
     }
 
     fn total(&self) -> Result<u64, ReadoutError> {
-        Ok(shared::get_meminfo_value("MemTotal"))
+        Ok(shared::read_meminfo()?.total)
     }
 
     fn free(&self) -> Result<u64, ReadoutError> {
-        Ok(shared::get_meminfo_value("MemFree"))
+        Ok(shared::read_meminfo()?.free)
     }
 
     fn buffers(&self) -> Result<u64, ReadoutError> {
@@ -391,6 +368,10 @@ impl MemoryReadout for NetBSDMemoryReadout {
     fn swap_used(&self) -> Result<u64, ReadoutError> {
         return Err(ReadoutError::NotImplemented);
     }
+
+    fn breakdown(&self) -> Result<MemoryBreakdown, ReadoutError> {
+        shared::read_meminfo()
+    }
 }
 
 impl ProductReadout for NetBSDProductReadout {
@@ -399,37 +380,19 @@ impl ProductReadout for NetBSDProductReadout {
     }
 
     fn product(&self) -> Result<String, ReadoutError> {
-        let output = Command::new("sysctl")
-            .args(["-n", "-b", "machdep.dmi.system-version"])
-            .output()
-            .expect("ERROR: failed to start \"sysctl\" process");
-
-        let sysver = String::from_utf8(output.stdout)
-            .expect("ERROR: \"sysctl\" process stdout was not valid UTF-8");
+        let sysver = command::run("sysctl", ["-n", "-b", "machdep.dmi.system-version"])?;
 
         Ok(sysver)
     }
 
     fn vendor(&self) -> Result<String, ReadoutError> {
-        let output = Command::new("sysctl")
-            .args(["-n", "-b", "machdep.dmi.system-vendor"])
-            .output()
-            .expect("ERROR: failed to start \"sysctl\" process");
-
-        let sysven = String::from_utf8(output.stdout)
-            .expect("ERROR: \"sysctl\" process stdout was not valid UTF-8");
+        let sysven = command::run("sysctl", ["-n", "-b", "machdep.dmi.system-vendor"])?;
 
         Ok(sysven)
     }
 
     fn family(&self) -> Result<String, ReadoutError> {
-        let output = Command::new("sysctl")
-            .args(["-n", "-b", "machdep.dmi.system-product"])
-            .output()
-            .expect("ERROR: failed to start \"sysctl\" process");
-
-        let sysprod = String::from_utf8(output.stdout)
-            .expect("ERROR: \"sysctl\" process stdout was not valid UTF-8");
+        let sysprod = command::run("sysctl", ["-n", "-b", "machdep.dmi.system-product"])?;
 
         Ok(sysprod)
     }
@@ -506,3 +469,50 @@ impl NetworkReadout for NetBSDNetworkReadout {
         Err(ReadoutError::NotImplemented)
     }
 }
+
+impl SensorReadout for NetBSDSensorReadout {
+    fn new() -> Self {
+        NetBSDSensorReadout
+    }
+}
+
+/// The metrics implemented by this backend.
+pub(crate) const CAPABILITIES: &[Capability] = &[
+    Capability::new(ReadoutKind::Battery, "percentage")
+        .requires(&[Prerequisite::Program("envstat")]),
+    Capability::new(ReadoutKind::Battery, "status").requires(&[Prerequisite::Program("envstat")]),
+    Capability::new(ReadoutKind::Kernel, "os_release"),
+    Capability::new(ReadoutKind::Kernel, "os_type"),
+    Capability::new(ReadoutKind::Kernel, "pretty_kernel"),
+    Capability::new(ReadoutKind::Memory, "total"),
+    Capability::new(ReadoutKind::Memory, "free"),
+    Capability::new(ReadoutKind::Memory, "used"),
+    Capability::new(ReadoutKind::Memory, "breakdown"),
+    Capability::new(ReadoutKind::General, "backlight"),
+    Capability::new(ReadoutKind::General, "resolution").requires(&[Prerequisite::X11Server]),
+    Capability::new(ReadoutKind::General, "username"),
+    Capability::new(ReadoutKind::General, "hostname"),
+    Capability::new(ReadoutKind::General, "distribution"),
+    Capability::new(ReadoutKind::General, "desktop_environment"),
+    Capability::new(ReadoutKind::General, "session"),
+    Capability::new(ReadoutKind::General, "window_manager").requires(&[
+        Prerequisite::Program("xprop"),
+        Prerequisite::Program("wmctrl"),
+    ]),
+    Capability::new(ReadoutKind::General, "terminal"),
+    Capability::new(ReadoutKind::General, "shell"),
+    Capability::new(ReadoutKind::General, "cpu_model_name"),
+    Capability::new(ReadoutKind::General, "cpu_usage"),
+    Capability::new(ReadoutKind::General, "load_average"),
+    Capability::new(ReadoutKind::General, "cpu_physical_cores"),
+    Capability::new(ReadoutKind::General, "cpu_cores"),
+    Capability::new(ReadoutKind::General, "uptime"),
+    Capability::new(ReadoutKind::General, "machine"),
+    Capability::new(ReadoutKind::General, "os_name"),
+    Capability::new(ReadoutKind::General, "disk_space"),
+    Capability::new(ReadoutKind::Product, "vendor"),
+    Capability::new(ReadoutKind::Product, "family"),
+    Capability::new(ReadoutKind::Product, "product"),
+    Capability::new(ReadoutKind::Package, "count_pkgs"),
+    Capability::new(ReadoutKind::Network, "logical_address"),
+];
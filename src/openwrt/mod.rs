@@ -1,11 +1,13 @@
 #![allow(clippy::unnecessary_cast)]
 mod sysinfo_ffi;
 
+use crate::capabilities::{Capability, ReadoutKind};
 use crate::shared;
 use crate::traits::*;
 use std::fs;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
+use std::time::Duration;
 use sysctl::{Ctl, Sysctl};
 use sysinfo_ffi::sysinfo;
 
@@ -28,6 +30,7 @@ pub struct OpenWrtMemoryReadout {
 pub struct OpenWrtProductReadout;
 pub struct OpenWrtPackageReadout;
 pub struct OpenWrtNetworkReadout;
+pub struct OpenWrtSensorReadout;
 
 impl BatteryReadout for OpenWrtBatteryReadout {
     fn new() -> Self {
@@ -152,23 +155,7 @@ impl GeneralReadout for OpenWrtGeneralReadout {
     }
 
     fn cpu_model_name(&self) -> Result<String, ReadoutError> {
-        let file = fs::File::open("/proc/cpuinfo");
-        if let Ok(content) = file {
-            let reader = BufReader::new(content);
-            for line in reader.lines().map_while(Result::ok) {
-                if line.starts_with("cpu model") {
-                    return Ok(line
-                        .replace("cpu model", "")
-                        .replace(':', "")
-                        .trim()
-                        .to_string());
-                }
-            }
-        }
-
-        Err(ReadoutError::Other(String::from(
-            "Cannot read model from /proc/cpuinfo",
-        )))
+        shared::cpu_model_name()
     }
 
     fn cpu_cores(&self) -> Result<usize, ReadoutError> {
@@ -195,6 +182,14 @@ impl GeneralReadout for OpenWrtGeneralReadout {
         }
     }
 
+    fn cpu_stats(&self, interval: Duration) -> Result<CpuStats, ReadoutError> {
+        shared::cpu_stats_at(Path::new("/"), interval)
+    }
+
+    fn load_average(&self) -> Result<LoadAverage, ReadoutError> {
+        shared::load_average_at(Path::new("/"))
+    }
+
     fn uptime(&self) -> Result<usize, ReadoutError> {
         let mut info = self.sysinfo;
         let info_ptr: *mut sysinfo = &mut info;
@@ -268,11 +263,13 @@ impl MemoryReadout for OpenWrtMemoryReadout {
     }
 
     fn cached(&self) -> Result<u64, ReadoutError> {
-        Ok(shared::get_meminfo_value("Cached"))
+        Ok(shared::read_meminfo()?.cached)
     }
 
     fn reclaimable(&self) -> Result<u64, ReadoutError> {
-        Ok(shared::get_meminfo_value("SReclaimable"))
+        shared::read_meminfo()?
+            .slab_reclaimable
+            .ok_or(ReadoutError::MetricNotAvailable)
     }
 
     fn used(&self) -> Result<u64, ReadoutError> {
@@ -300,6 +297,28 @@ impl MemoryReadout for OpenWrtMemoryReadout {
     fn swap_used(&self) -> Result<u64, ReadoutError> {
         Err(ReadoutError::NotImplemented)
     }
+
+    fn breakdown(&self) -> Result<MemoryBreakdown, ReadoutError> {
+        shared::read_meminfo()
+    }
+}
+
+impl ProductReadout for OpenWrtProductReadout {
+    fn new() -> Self {
+        OpenWrtProductReadout
+    }
+
+    fn vendor(&self) -> Result<String, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    fn family(&self) -> Result<String, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    fn product(&self) -> Result<String, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
 }
 
 impl PackageReadout for OpenWrtPackageReadout {
@@ -370,3 +389,38 @@ impl NetworkReadout for OpenWrtNetworkReadout {
         Err(ReadoutError::NotImplemented)
     }
 }
+
+impl SensorReadout for OpenWrtSensorReadout {
+    fn new() -> Self {
+        OpenWrtSensorReadout
+    }
+}
+
+/// The metrics implemented by this backend.
+pub(crate) const CAPABILITIES: &[Capability] = &[
+    Capability::new(ReadoutKind::Kernel, "os_release"),
+    Capability::new(ReadoutKind::Kernel, "os_type"),
+    Capability::new(ReadoutKind::Kernel, "pretty_kernel"),
+    Capability::new(ReadoutKind::Memory, "total"),
+    Capability::new(ReadoutKind::Memory, "free"),
+    Capability::new(ReadoutKind::Memory, "buffers"),
+    Capability::new(ReadoutKind::Memory, "cached"),
+    Capability::new(ReadoutKind::Memory, "reclaimable"),
+    Capability::new(ReadoutKind::Memory, "used"),
+    Capability::new(ReadoutKind::Memory, "breakdown"),
+    Capability::new(ReadoutKind::General, "username"),
+    Capability::new(ReadoutKind::General, "hostname"),
+    Capability::new(ReadoutKind::General, "distribution"),
+    Capability::new(ReadoutKind::General, "shell"),
+    Capability::new(ReadoutKind::General, "cpu_model_name"),
+    Capability::new(ReadoutKind::General, "cpu_usage"),
+    Capability::new(ReadoutKind::General, "cpu_stats"),
+    Capability::new(ReadoutKind::General, "load_average"),
+    Capability::new(ReadoutKind::General, "cpu_physical_cores"),
+    Capability::new(ReadoutKind::General, "cpu_cores"),
+    Capability::new(ReadoutKind::General, "uptime"),
+    Capability::new(ReadoutKind::General, "machine"),
+    Capability::new(ReadoutKind::General, "disk_space"),
+    Capability::new(ReadoutKind::Package, "count_pkgs"),
+    Capability::new(ReadoutKind::Network, "logical_address"),
+];
@@ -0,0 +1,320 @@
+//! This module parses `/proc/cpuinfo` and identifies the CPU it describes. Its layout differs
+//! between architectures: x86 names the CPU in `model name`, ARM only reports the numeric IDs
+//! of its implementer and part, RISC-V its micro-architecture and POWER and MIPS use `cpu` and
+//! `cpu model` respectively. The device tree is consulted where `/proc/cpuinfo` falls short.
+
+use crate::traits::{ReadoutError, ReadoutErrorKind};
+use std::path::Path;
+
+/// A block of `key : value` lines in `/proc/cpuinfo`, which usually describes one processor.
+/// Some architectures append a block describing the whole system, _e.g._ `Hardware` on ARM.
+#[derive(Debug, Default)]
+pub(crate) struct CpuInfoBlock {
+    fields: Vec<(String, String)>,
+}
+
+impl CpuInfoBlock {
+    /// Returns the value of the first field named `key`, if it isn't empty.
+    pub(crate) fn get(&self, key: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Returns the number of the processor the block describes.
+    pub(crate) fn processor(&self) -> Option<usize> {
+        self.get("processor")?.parse().ok()
+    }
+
+    /// Returns the name of the processor the block describes, if it names one.
+    pub(crate) fn model_name(&self) -> Option<String> {
+        let implementer = self.get("CPU implementer").and_then(parse_hex);
+        let part = self.get("CPU part").and_then(parse_hex);
+
+        if let Some(name) = implementer.zip(part).and_then(|(i, p)| arm_core_name(i, p)) {
+            return Some(name);
+        }
+
+        self.get("model name")
+            .or_else(|| self.get("cpu model"))
+            .map(str::to_owned)
+            .or_else(|| self.get("uarch").map(describe_compatible))
+            .or_else(|| {
+                // _e.g._ `POWER9 (raw), altivec supported`
+                let cpu = self.get("cpu")?.split(',').next()?.trim();
+                Some(cpu.to_owned())
+            })
+    }
+}
+
+/// Splits the contents of `/proc/cpuinfo` into its blocks.
+pub(crate) fn parse_cpuinfo(content: &str) -> Vec<CpuInfoBlock> {
+    let mut blocks = Vec::new();
+    let mut fields = Vec::new();
+
+    // A trailing empty line ends the last block.
+    for line in content.lines().chain([""]) {
+        match line.split_once(':') {
+            Some((key, value)) => fields.push((key.trim().to_owned(), value.trim().to_owned())),
+            None if line.trim().is_empty() && !fields.is_empty() => {
+                blocks.push(CpuInfoBlock {
+                    fields: std::mem::take(&mut fields),
+                });
+            }
+            None => {}
+        }
+    }
+
+    blocks
+}
+
+/// Reads `proc/cpuinfo` under `root` and splits it into its blocks.
+pub(crate) fn read_cpuinfo(root: &Path) -> Result<Vec<CpuInfoBlock>, ReadoutError> {
+    Ok(parse_cpuinfo(&super::read_file(root.join("proc/cpuinfo"))?))
+}
+
+fn parse_hex(value: &str) -> Option<u32> {
+    u32::from_str_radix(value.strip_prefix("0x")?, 16).ok()
+}
+
+/// Returns the name of an ARM core from the implementer and part numbers of its `MIDR`
+/// register, or the name of its implementer if the part is unknown.
+fn arm_core_name(implementer: u32, part: u32) -> Option<String> {
+    const PARTS: &[(u32, u32, &str)] = &[
+        (0x41, 0xc07, "Cortex-A7"),
+        (0x41, 0xc08, "Cortex-A8"),
+        (0x41, 0xc09, "Cortex-A9"),
+        (0x41, 0xc0d, "Cortex-A12"),
+        (0x41, 0xc0e, "Cortex-A17"),
+        (0x41, 0xc0f, "Cortex-A15"),
+        (0x41, 0xd01, "Cortex-A32"),
+        (0x41, 0xd03, "Cortex-A53"),
+        (0x41, 0xd04, "Cortex-A35"),
+        (0x41, 0xd05, "Cortex-A55"),
+        (0x41, 0xd06, "Cortex-A65"),
+        (0x41, 0xd07, "Cortex-A57"),
+        (0x41, 0xd08, "Cortex-A72"),
+        (0x41, 0xd09, "Cortex-A73"),
+        (0x41, 0xd0a, "Cortex-A75"),
+        (0x41, 0xd0b, "Cortex-A76"),
+        (0x41, 0xd0c, "Neoverse-N1"),
+        (0x41, 0xd0d, "Cortex-A77"),
+        (0x41, 0xd0e, "Cortex-A76AE"),
+        (0x41, 0xd40, "Neoverse-V1"),
+        (0x41, 0xd41, "Cortex-A78"),
+        (0x41, 0xd42, "Cortex-A78AE"),
+        (0x41, 0xd44, "Cortex-X1"),
+        (0x41, 0xd46, "Cortex-A510"),
+        (0x41, 0xd47, "Cortex-A710"),
+        (0x41, 0xd48, "Cortex-X2"),
+        (0x41, 0xd49, "Neoverse-N2"),
+        (0x41, 0xd4a, "Neoverse-E1"),
+        (0x41, 0xd4b, "Cortex-A78C"),
+        (0x41, 0xd4c, "Cortex-X1C"),
+        (0x41, 0xd4d, "Cortex-A715"),
+        (0x41, 0xd4e, "Cortex-X3"),
+        (0x41, 0xd4f, "Neoverse-V2"),
+        (0x41, 0xd80, "Cortex-A520"),
+        (0x41, 0xd81, "Cortex-A720"),
+        (0x41, 0xd82, "Cortex-X4"),
+        (0x41, 0xd84, "Neoverse-V3"),
+        (0x41, 0xd8e, "Neoverse-N3"),
+        (0x42, 0x100, "Broadcom Brahma-B53"),
+        (0x42, 0x516, "Broadcom Vulcan"),
+        (0x43, 0x0a1, "Cavium ThunderX"),
+        (0x43, 0x0af, "Cavium ThunderX2"),
+        (0x46, 0x001, "Fujitsu A64FX"),
+        (0x48, 0xd01, "HiSilicon TaiShan v110"),
+        (0x4e, 0x003, "NVIDIA Denver 2"),
+        (0x4e, 0x004, "NVIDIA Carmel"),
+        (0x51, 0x001, "Qualcomm Oryon"),
+        (0x51, 0x800, "Qualcomm Kryo 2xx Gold"),
+        (0x51, 0x801, "Qualcomm Kryo 2xx Silver"),
+        (0x51, 0x802, "Qualcomm Kryo 3xx Gold"),
+        (0x51, 0x803, "Qualcomm Kryo 3xx Silver"),
+        (0x51, 0x804, "Qualcomm Kryo 4xx Gold"),
+        (0x51, 0x805, "Qualcomm Kryo 4xx Silver"),
+        (0x51, 0xc00, "Qualcomm Falkor"),
+        (0x61, 0x022, "Apple Icestorm"),
+        (0x61, 0x023, "Apple Firestorm"),
+        (0x61, 0x024, "Apple Icestorm"),
+        (0x61, 0x025, "Apple Firestorm"),
+        (0x61, 0x028, "Apple Icestorm"),
+        (0x61, 0x029, "Apple Firestorm"),
+        (0x61, 0x032, "Apple Blizzard"),
+        (0x61, 0x033, "Apple Avalanche"),
+        (0x61, 0x034, "Apple Blizzard"),
+        (0x61, 0x035, "Apple Avalanche"),
+        (0x61, 0x038, "Apple Blizzard"),
+        (0x61, 0x039, "Apple Avalanche"),
+        (0xc0, 0xac3, "Ampere-1"),
+        (0xc0, 0xac4, "Ampere-1a"),
+    ];
+
+    const IMPLEMENTERS: &[(u32, &str)] = &[
+        (0x41, "ARM"),
+        (0x42, "Broadcom"),
+        (0x43, "Cavium"),
+        (0x46, "Fujitsu"),
+        (0x48, "HiSilicon"),
+        (0x4e, "NVIDIA"),
+        (0x50, "Applied Micro"),
+        (0x51, "Qualcomm"),
+        (0x53, "Samsung"),
+        (0x56, "Marvell"),
+        (0x61, "Apple"),
+        (0x69, "Intel"),
+        (0xc0, "Ampere"),
+    ];
+
+    if let Some((_, _, name)) = PARTS
+        .iter()
+        .find(|(i, p, _)| *i == implementer && *p == part)
+    {
+        return Some(name.to_string());
+    }
+
+    IMPLEMENTERS
+        .iter()
+        .find(|(i, _)| *i == implementer)
+        .map(|(_, name)| format!("{name} part {part:#05x}"))
+}
+
+/// Turns a device tree `compatible` string such as `brcm,bcm2711` into `Broadcom BCM2711`.
+fn describe_compatible(compatible: &str) -> String {
+    const VENDORS: &[(&str, &str)] = &[
+        ("allwinner", "Allwinner"),
+        ("amlogic", "Amlogic"),
+        ("apple", "Apple"),
+        ("arm", "ARM"),
+        ("brcm", "Broadcom"),
+        ("fsl", "NXP"),
+        ("mediatek", "MediaTek"),
+        ("nvidia", "NVIDIA"),
+        ("nxp", "NXP"),
+        ("qcom", "Qualcomm"),
+        ("rockchip", "Rockchip"),
+        ("samsung", "Samsung"),
+        ("sifive", "SiFive"),
+        ("starfive", "StarFive"),
+        ("thead", "T-Head"),
+        ("ti", "Texas Instruments"),
+    ];
+
+    match compatible.split_once(',') {
+        Some((vendor, model)) => {
+            let vendor = VENDORS
+                .iter()
+                .find(|(prefix, _)| *prefix == vendor)
+                .map_or(vendor, |(_, name)| name);
+            format!("{vendor} {}", model.to_uppercase())
+        }
+        None => compatible.to_owned(),
+    }
+}
+
+/// Returns the first entry of a device tree `compatible` property, skipping generic entries
+/// such as `riscv`.
+fn read_compatible(root: &Path, path: &str) -> Option<String> {
+    let content = std::fs::read(root.join(path)).ok()?;
+
+    content
+        .split(|&byte| byte == 0)
+        .filter_map(|entry| std::str::from_utf8(entry).ok())
+        .find(|entry| entry.contains(','))
+        .map(describe_compatible)
+}
+
+/// Identifies the CPU from `proc/cpuinfo` under `root`. CPUs with different kinds of cores,
+/// _e.g._ ARM big.LITTLE SoCs, are named after each of them, _e.g._
+/// `Cortex-A55 + Cortex-A76`.
+pub(crate) fn cpu_model_name_at(root: &Path) -> Result<String, ReadoutError> {
+    let blocks = read_cpuinfo(root)?;
+    let mut names: Vec<String> = Vec::new();
+
+    for name in blocks.iter().filter_map(CpuInfoBlock::model_name) {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    if !names.is_empty() {
+        return Ok(names.join(" + "));
+    }
+
+    let field = |key: &str| blocks.iter().find_map(|block| block.get(key));
+
+    field("Hardware")
+        .or_else(|| field("Processor"))
+        .map(str::to_owned)
+        .or_else(|| read_compatible(root, "proc/device-tree/cpus/cpu@0/compatible"))
+        .or_else(|| read_compatible(root, "proc/device-tree/compatible"))
+        .or_else(|| {
+            // _e.g._ `rv64imafdc_zicntr_zicsr`, of which only the base extensions are kept.
+            let isa = field("isa")?;
+            Some(isa.split('_').next().unwrap_or(isa).to_owned())
+        })
+        .ok_or_else(|| {
+            ReadoutError::failed(ReadoutErrorKind::NotFound)
+                .with_metric("cpu_model_name")
+                .with_path(root.join("proc/cpuinfo"))
+                .with_message("Could not identify the CPU")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_model_name() {
+        let name = |cpuinfo: &str| -> Vec<Option<String>> {
+            parse_cpuinfo(cpuinfo)
+                .iter()
+                .map(CpuInfoBlock::model_name)
+                .collect()
+        };
+
+        assert_eq!(
+            name(
+                "processor\t: 0\nvendor_id\t: AuthenticAMD\n\
+                 model name\t: AMD Ryzen 7 5800X 8-Core Processor\n"
+            ),
+            [Some("AMD Ryzen 7 5800X 8-Core Processor".to_owned())]
+        );
+        assert_eq!(
+            name(
+                "processor\t: 0\nCPU implementer\t: 0x41\nCPU part\t: 0xd0b\n\n\
+                 processor\t: 1\nCPU implementer\t: 0x61\nCPU part\t: 0x023\n\n\
+                 processor\t: 2\nCPU implementer\t: 0x51\nCPU part\t: 0xfff\n\n\
+                 Hardware\t: Qualcomm Technologies, Inc SM8250\n"
+            ),
+            [
+                Some("Cortex-A76".to_owned()),
+                Some("Apple Firestorm".to_owned()),
+                Some("Qualcomm part 0xfff".to_owned()),
+                None
+            ]
+        );
+        assert_eq!(
+            name(
+                "processor\t: 0\nhart\t\t: 1\n\
+                 isa\t\t: rv64imafdc_zicsr\nuarch\t\t: sifive,u74-mc\n"
+            ),
+            [Some("SiFive U74-MC".to_owned())]
+        );
+        assert_eq!(
+            name("processor\t: 0\ncpu\t\t: POWER9 (raw), altivec supported\n"),
+            [Some("POWER9 (raw)".to_owned())]
+        );
+        assert_eq!(
+            name(
+                "system type\t\t: MediaTek MT7621 ver:1 eco:3\n\
+                 processor\t\t: 0\ncpu model\t\t: MIPS 1004Kc V2.15\n"
+            ),
+            [Some("MIPS 1004Kc V2.15".to_owned())]
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! This module parses `/proc/meminfo` in a single pass.
+
+use crate::traits::{MemoryBreakdown, ReadoutError, ReadoutErrorKind};
+use std::path::Path;
+
+/// Parses the contents of `/proc/meminfo`. Lines are of the form `MemTotal: 16318460 kB`, except
+/// for the huge page counters, which have no unit. Only the fields that are of interest are
+/// parsed, so that unknown lines, _e.g._ the legacy header NetBSD emits, are ignored.
+pub(crate) fn parse_meminfo(content: &str) -> Result<MemoryBreakdown, ReadoutError> {
+    let fields: Vec<(&str, &str)> = content
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .collect();
+
+    let field = |key: &str| -> Result<Option<u64>, ReadoutError> {
+        let Some((_, value)) = fields.iter().find(|(k, _)| *k == key) else {
+            return Ok(None);
+        };
+
+        let number = value.strip_suffix("kB").unwrap_or(value).trim();
+        number.parse().map(Some).map_err(|e| {
+            ReadoutError::from_source(ReadoutErrorKind::ParseError, e)
+                .with_message(format!("Could not parse the value of {key}: '{value}'"))
+        })
+    };
+
+    let required = |key: &str| -> Result<u64, ReadoutError> {
+        field(key)?.ok_or_else(|| {
+            ReadoutError::failed(ReadoutErrorKind::ParseError)
+                .with_message(format!("{key} is missing"))
+        })
+    };
+
+    Ok(MemoryBreakdown {
+        total: required("MemTotal")?,
+        free: required("MemFree")?,
+        available: field("MemAvailable")?,
+        buffers: required("Buffers")?,
+        cached: required("Cached")?,
+        shared: field("Shmem")?,
+        active: field("Active")?,
+        inactive: field("Inactive")?,
+        dirty: field("Dirty")?,
+        writeback: field("Writeback")?,
+        slab_reclaimable: field("SReclaimable")?,
+        slab_unreclaimable: field("SUnreclaim")?,
+        page_tables: field("PageTables")?,
+        committed: field("Committed_AS")?,
+        commit_limit: field("CommitLimit")?,
+        swap_total: required("SwapTotal")?,
+        swap_free: required("SwapFree")?,
+        swap_cached: field("SwapCached")?,
+        huge_pages_total: field("HugePages_Total")?,
+        huge_pages_free: field("HugePages_Free")?,
+        huge_page_size: field("Hugepagesize")?,
+        zswap: field("Zswap")?,
+        zswapped: field("Zswapped")?,
+    })
+}
+
+/// Reads and parses `proc/meminfo` under `root`.
+pub(crate) fn read_meminfo_at(root: &Path) -> Result<MemoryBreakdown, ReadoutError> {
+    let path = root.join("proc/meminfo");
+    let content = super::read_file(&path)?;
+    parse_meminfo(&content).map_err(|e| e.with_path(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_meminfo() {
+        let meminfo = parse_meminfo(
+            "MemTotal:       16318460 kB\n\
+             MemFree:         1034880 kB\n\
+             MemAvailable:    9871204 kB\n\
+             Buffers:          412560 kB\n\
+             Cached:          8402136 kB\n\
+             SwapCached:         1024 kB\n\
+             Active:          6145332 kB\n\
+             Inactive:        7345804 kB\n\
+             SwapTotal:       8388604 kB\n\
+             SwapFree:        8125436 kB\n\
+             Shmem:            690412 kB\n\
+             SReclaimable:     498760 kB\n\
+             SUnreclaim:       151828 kB\n\
+             HugePages_Total:       4\n\
+             HugePages_Free:        2\n\
+             Hugepagesize:       2048 kB\n",
+        )
+        .unwrap();
+
+        assert_eq!(meminfo.used(), 16318460 - 9871204);
+        assert_eq!(meminfo.swap_used(), 8388604 - 8125436);
+        assert_eq!(meminfo.shared, Some(690412));
+        assert_eq!(meminfo.slab_unreclaimable, Some(151828));
+        assert_eq!(meminfo.huge_pages_total, Some(4));
+        assert_eq!(meminfo.huge_page_size, Some(2048));
+        assert_eq!(meminfo.dirty, None);
+
+        let error = parse_meminfo("MemTotal: 1024 kB\n").unwrap_err();
+        assert_eq!(error.kind(), Some(ReadoutErrorKind::ParseError));
+        assert!(parse_meminfo("MemTotal: lots\n").is_err());
+        assert!(parse_meminfo(
+            "        total:    used:\nMem:  4096 1024\nMemTotal: 4 kB\nMemFree: 1 kB\n\
+             Buffers: 0 kB\nCached: 0 kB\nSwapTotal: 0 kB\nSwapFree: 0 kB\n"
+        )
+        .is_ok());
+    }
+}
@@ -2,13 +2,21 @@
 #![allow(unused_imports)]
 #![allow(clippy::unnecessary_cast)]
 
-use crate::traits::{ReadoutError, ShellFormat, ShellKind};
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "netbsd"))]
+mod cpuinfo;
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "netbsd"))]
+mod meminfo;
+#[cfg(target_os = "linux")]
+mod proc_stat;
+
+use crate::traits::{
+    LoadAverage, MemoryBreakdown, ReadoutError, ReadoutErrorKind, ShellFormat, ShellKind,
+};
 
 use std::fs::read_dir;
 use std::fs::read_to_string;
 use std::io::Error;
 use std::path::Path;
-use std::process::{Command, Stdio};
 use std::{env, fs};
 use std::{ffi::CStr, path::PathBuf};
 
@@ -16,19 +24,54 @@ use std::ffi::CString;
 #[cfg(any(target_os = "linux", target_os = "macos", target_os = "android"))]
 use sysctl::SysctlError;
 
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "netbsd"))]
+pub(crate) use cpuinfo::{cpu_model_name_at, read_cpuinfo, CpuInfoBlock};
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "netbsd"))]
+pub(crate) use meminfo::read_meminfo_at;
+#[cfg(target_os = "linux")]
+pub(crate) use proc_stat::cpu_stats_at;
+
 #[cfg(any(target_os = "linux", target_os = "macos", target_os = "android"))]
 impl From<SysctlError> for ReadoutError {
     fn from(e: SysctlError) -> Self {
-        ReadoutError::Other(format!("Could not access sysctl: {e:?}"))
+        let kind = match e {
+            SysctlError::NotFound(_) => ReadoutErrorKind::NotFound,
+            SysctlError::IoError(ref e) => io_error_kind(e),
+            SysctlError::ParseError | SysctlError::Utf8Error(_) => ReadoutErrorKind::ParseError,
+            SysctlError::NoReadAccess => ReadoutErrorKind::PermissionDenied,
+            SysctlError::NotSupported => ReadoutErrorKind::Unsupported,
+            _ => ReadoutErrorKind::Other,
+        };
+
+        let message = format!("Could not access sysctl: {e:?}");
+        ReadoutError::from_source(kind, e).with_message(message)
     }
 }
 
 impl From<std::io::Error> for ReadoutError {
     fn from(e: Error) -> Self {
-        ReadoutError::Other(e.to_string())
+        ReadoutError::from_source(io_error_kind(&e), e)
+    }
+}
+
+/// Maps the kind of an I/O error onto the kind of a readout failure.
+pub(crate) fn io_error_kind(e: &Error) -> ReadoutErrorKind {
+    match e.kind() {
+        std::io::ErrorKind::NotFound => ReadoutErrorKind::NotFound,
+        std::io::ErrorKind::PermissionDenied => ReadoutErrorKind::PermissionDenied,
+        std::io::ErrorKind::TimedOut => ReadoutErrorKind::Timeout,
+        std::io::ErrorKind::InvalidData => ReadoutErrorKind::ParseError,
+        std::io::ErrorKind::Unsupported => ReadoutErrorKind::Unsupported,
+        _ => ReadoutErrorKind::Io,
     }
 }
 
+/// Reads the file at `path`, recording the path in the error if that fails.
+pub(crate) fn read_file<P: AsRef<Path>>(path: P) -> Result<String, ReadoutError> {
+    let path = path.as_ref();
+    fs::read_to_string(path).map_err(|e| ReadoutError::from(e).with_path(path))
+}
+
 #[cfg(not(any(target_os = "freebsd", target_os = "macos", target_os = "windows")))]
 pub(crate) fn uptime() -> Result<usize, ReadoutError> {
     let uptime_buf = fs::read_to_string("/proc/uptime")?;
@@ -195,26 +238,9 @@ pub(crate) fn shell(shorthand: ShellFormat, kind: ShellKind) -> Result<String, R
     }
 }
 
-#[cfg(not(any(target_os = "macos", target_os = "windows")))]
-pub(crate) fn cpu_model_name() -> String {
-    use std::io::{BufRead, BufReader};
-    let file = fs::File::open("/proc/cpuinfo");
-    match file {
-        Ok(content) => {
-            let reader = BufReader::new(content);
-            for line in reader.lines().map_while(Result::ok) {
-                if line.starts_with("model name") {
-                    return line
-                        .replace("model name", "")
-                        .replace(':', "")
-                        .trim()
-                        .to_string();
-                }
-            }
-            String::new()
-        }
-        Err(_e) => String::new(),
-    }
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "netbsd"))]
+pub(crate) fn cpu_model_name() -> Result<String, ReadoutError> {
+    cpu_model_name_at(Path::new("/"))
 }
 
 #[cfg(any(target_os = "freebsd", target_os = "macos", target_os = "netbsd"))]
@@ -233,6 +259,59 @@ pub(crate) fn cpu_usage() -> Result<usize, ReadoutError> {
     )))
 }
 
+#[cfg(any(target_os = "freebsd", target_os = "macos", target_os = "netbsd"))]
+pub(crate) fn load_average() -> Result<LoadAverage, ReadoutError> {
+    let mut loads = [0f64; 3];
+    let ret = unsafe { libc::getloadavg(loads.as_mut_ptr(), 3) };
+    if ret != 3 {
+        return Err(ReadoutError::failed(ReadoutErrorKind::Other)
+            .with_metric("load_average")
+            .with_message(format!("getloadavg failed with return code: {ret}")));
+    }
+
+    Ok(LoadAverage {
+        one: loads[0],
+        five: loads[1],
+        fifteen: loads[2],
+        runnable_tasks: None,
+        total_tasks: None,
+    })
+}
+
+/// Parses `proc/loadavg` under `root`, e.g. `0.52 0.58 0.59 2/1234 56789`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn load_average_at(root: &Path) -> Result<LoadAverage, ReadoutError> {
+    let path = root.join("proc/loadavg");
+    let content = read_file(&path)?;
+    let fields: Vec<&str> = content.split_whitespace().collect();
+
+    let parse_error = || {
+        ReadoutError::failed(ReadoutErrorKind::ParseError)
+            .with_message(format!(
+                "Could not parse the load average '{}'",
+                content.trim()
+            ))
+            .with_path(&path)
+    };
+
+    let load = |i: usize| -> Result<f64, ReadoutError> {
+        fields
+            .get(i)
+            .and_then(|f| f.parse().ok())
+            .ok_or_else(parse_error)
+    };
+
+    let tasks = fields.get(3).and_then(|f| f.split_once('/'));
+
+    Ok(LoadAverage {
+        one: load(0)?,
+        five: load(1)?,
+        fifteen: load(2)?,
+        runnable_tasks: tasks.and_then(|(r, _)| r.parse().ok()),
+        total_tasks: tasks.and_then(|(_, t)| t.parse().ok()),
+    })
+}
+
 #[cfg(target_family = "unix")]
 pub(crate) fn cpu_cores() -> Result<usize, ReadoutError> {
     Ok(num_cpus::get())
@@ -283,24 +362,10 @@ pub(crate) fn disk_space(path: &Path) -> Result<(u64, u64), ReadoutError> {
     )))
 }
 
-/// Obtain the value of a specified field from `/proc/meminfo` needed to calculate memory usage
-#[cfg(not(any(target_os = "macos", target_os = "windows")))]
-pub(crate) fn get_meminfo_value(value: &str) -> u64 {
-    use std::io::{BufRead, BufReader};
-    let file = fs::File::open("/proc/meminfo");
-    match file {
-        Ok(content) => {
-            let reader = BufReader::new(content);
-            for line in reader.lines().map_while(Result::ok) {
-                if line.starts_with(value) {
-                    let s_mem_kb: String = line.chars().filter(|c| c.is_ascii_digit()).collect();
-                    return s_mem_kb.parse::<u64>().unwrap_or(0);
-                }
-            }
-            0
-        }
-        Err(_e) => 0,
-    }
+/// Reads and parses `/proc/meminfo`.
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "netbsd"))]
+pub(crate) fn read_meminfo() -> Result<MemoryBreakdown, ReadoutError> {
+    read_meminfo_at(Path::new("/"))
 }
 
 #[cfg(not(target_os = "windows"))]
@@ -330,8 +395,12 @@ pub(crate) fn logical_address(interface: Option<&str>) -> Result<String, Readout
 }
 
 pub(crate) fn count_cargo() -> Option<usize> {
-    let bin = home::cargo_home().ok()?.join("bin");
-    let read_dir = read_dir(bin).ok()?;
+    count_cargo_in(&home::cargo_home().ok()?)
+}
+
+/// Counts the binaries installed in `cargo_home`, the directory `$CARGO_HOME` points to.
+pub(crate) fn count_cargo_in(cargo_home: &Path) -> Option<usize> {
+    let read_dir = read_dir(cargo_home.join("bin")).ok()?;
 
     match read_dir.count() {
         0 => None,
@@ -0,0 +1,210 @@
+//! This module samples `/proc/stat` to measure CPU utilisation.
+
+use crate::traits::{CpuStats, CpuTimes, ReadoutError, ReadoutErrorKind};
+use std::path::Path;
+use std::time::Duration;
+
+/// The cumulative time a CPU has spent in each state since boot, in clock ticks.
+#[derive(Debug, Clone, Copy, Default)]
+struct Ticks {
+    user: u64,
+    nice: u64,
+    system: u64,
+    idle: u64,
+    iowait: u64,
+    irq: u64,
+    softirq: u64,
+    steal: u64,
+}
+
+impl Ticks {
+    fn total(&self) -> u64 {
+        self.user
+            + self.nice
+            + self.system
+            + self.idle
+            + self.iowait
+            + self.irq
+            + self.softirq
+            + self.steal
+    }
+
+    /// Returns how the time that passed between `earlier` and `self` was spent.
+    fn since(&self, earlier: &Ticks) -> CpuTimes {
+        let total = self.total().saturating_sub(earlier.total());
+        if total == 0 {
+            return CpuTimes::default();
+        }
+
+        let percent = |now: u64, then: u64| now.saturating_sub(then) as f64 * 100.0 / total as f64;
+
+        CpuTimes {
+            user: percent(self.user, earlier.user),
+            nice: percent(self.nice, earlier.nice),
+            system: percent(self.system, earlier.system),
+            idle: percent(self.idle, earlier.idle),
+            iowait: percent(self.iowait, earlier.iowait),
+            irq: percent(self.irq, earlier.irq),
+            softirq: percent(self.softirq, earlier.softirq),
+            steal: percent(self.steal, earlier.steal),
+        }
+    }
+}
+
+/// The contents of `/proc/stat` that are of interest.
+#[derive(Debug, Default)]
+struct Stat {
+    total: Ticks,
+    cores: Vec<(usize, Ticks)>,
+    context_switches: u64,
+    interrupts: u64,
+}
+
+fn parse_ticks<'a>(mut fields: impl Iterator<Item = &'a str>) -> Result<Ticks, ReadoutError> {
+    let mut next = || -> Result<u64, ReadoutError> {
+        // Older kernels report fewer columns, which are treated as zero.
+        match fields.next() {
+            Some(field) => field
+                .parse()
+                .map_err(|e| ReadoutError::from_source(ReadoutErrorKind::ParseError, e)),
+            None => Ok(0),
+        }
+    };
+
+    Ok(Ticks {
+        user: next()?,
+        nice: next()?,
+        system: next()?,
+        idle: next()?,
+        iowait: next()?,
+        irq: next()?,
+        softirq: next()?,
+        steal: next()?,
+    })
+}
+
+fn parse_stat(content: &str) -> Result<Stat, ReadoutError> {
+    let mut stat = Stat::default();
+    let mut found_total = false;
+
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let Some(key) = fields.next() else {
+            continue;
+        };
+
+        match key {
+            "cpu" => {
+                stat.total = parse_ticks(fields)?;
+                found_total = true;
+            }
+            "ctxt" | "intr" => {
+                // "intr" is followed by the per-interrupt counts, only the total is kept.
+                let value = fields
+                    .next()
+                    .unwrap_or_default()
+                    .parse()
+                    .map_err(|e| ReadoutError::from_source(ReadoutErrorKind::ParseError, e))?;
+
+                if key == "ctxt" {
+                    stat.context_switches = value;
+                } else {
+                    stat.interrupts = value;
+                }
+            }
+            _ => {
+                if let Some(Ok(cpu)) = key.strip_prefix("cpu").map(str::parse::<usize>) {
+                    stat.cores.push((cpu, parse_ticks(fields)?));
+                }
+            }
+        }
+    }
+
+    if !found_total {
+        return Err(ReadoutError::failed(ReadoutErrorKind::ParseError)
+            .with_message("No aggregate \"cpu\" line was found"));
+    }
+
+    Ok(stat)
+}
+
+fn read_stat(path: &Path) -> Result<Stat, ReadoutError> {
+    let content = super::read_file(path)?;
+    parse_stat(&content).map_err(|e| e.with_path(path))
+}
+
+/// Reads `proc/stat` under `root` at the start and the end of `interval`, and returns the CPU
+/// utilisation in between.
+pub(crate) fn cpu_stats_at(root: &Path, interval: Duration) -> Result<CpuStats, ReadoutError> {
+    let path = root.join("proc/stat");
+    let before = read_stat(&path)?;
+    std::thread::sleep(interval);
+    let after = read_stat(&path)?;
+
+    Ok(compare(&before, &after))
+}
+
+/// Computes the utilisation between two samples. CPUs that went on- or offline in between are
+/// left out.
+fn compare(before: &Stat, after: &Stat) -> CpuStats {
+    let cores = after
+        .cores
+        .iter()
+        .filter_map(|(cpu, now)| {
+            let (_, then) = before.cores.iter().find(|(c, _)| c == cpu)?;
+            Some((*cpu, now.since(then)))
+        })
+        .collect();
+
+    CpuStats {
+        total: after.total.since(&before.total),
+        cores,
+        context_switches: after.context_switches,
+        interrupts: after.interrupts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_samples() {
+        let before = parse_stat(
+            "cpu  100 0 100 700 100 0 0 0 0 0\n\
+             cpu0 50 0 50 350 50 0 0 0 0 0\n\
+             cpu1 50 0 50 350 50 0 0 0 0 0\n\
+             intr 1000 20 30\n\
+             ctxt 5000\n",
+        )
+        .unwrap();
+        let after = parse_stat(
+            "cpu  200 0 150 750 100 0 0 0 0 0\n\
+             cpu0 150 0 50 350 50 0 0 0 0 0\n\
+             cpu1 50 0 100 400 50 0 0 0 0 0\n\
+             intr 1100 25 35\n\
+             ctxt 5400\n",
+        )
+        .unwrap();
+
+        let stats = compare(&before, &after);
+        assert_eq!(stats.total.user, 50.0);
+        assert_eq!(stats.total.system, 25.0);
+        assert_eq!(stats.total.busy(), 75.0);
+        assert_eq!(
+            stats.cores[0],
+            (
+                0,
+                CpuTimes {
+                    user: 100.0,
+                    ..Default::default()
+                }
+            )
+        );
+        assert_eq!(stats.cores[1].1.idle, 50.0);
+        assert_eq!(stats.context_switches, 5400);
+        assert_eq!(stats.interrupts, 1100);
+
+        assert!(parse_stat("intr 1\n").is_err());
+    }
+}
@@ -0,0 +1,405 @@
+//! This module provides [`SystemSnapshot`], a single value holding the outcome of every readout
+//! exposed by [`Readouts`](crate::Readouts).
+//!
+//! When the `serde` feature is enabled, a snapshot can be serialized in one go, e.g. to ship a
+//! host's state as JSON. Every field keeps either its success value or the [`ReadoutError`] that
+//! was returned for it. Metrics that are missing from a deserialized snapshot default to
+//! [`ReadoutError::NotImplemented`].
+
+use crate::traits::*;
+use crate::Readouts;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+/// The interval CPU utilisation is sampled over when taking a snapshot, see
+/// [`GeneralReadout::cpu_stats`].
+pub const CPU_STATS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// The outcome of every battery readout.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct BatterySnapshot {
+    pub percentage: Result<u8, ReadoutError>,
+    pub status: Result<BatteryState, ReadoutError>,
+    pub ac_online: Result<bool, ReadoutError>,
+    pub health: Result<u8, ReadoutError>,
+    pub capacity: Result<BatteryCapacity, ReadoutError>,
+    pub power: Result<f64, ReadoutError>,
+    pub voltage: Result<f64, ReadoutError>,
+    pub time_to_empty: Result<Duration, ReadoutError>,
+    pub time_to_full: Result<Duration, ReadoutError>,
+    pub batteries: Result<Vec<Battery>, ReadoutError>,
+    pub combined_percentage: Result<u8, ReadoutError>,
+    pub peripherals: Result<Vec<PeripheralBattery>, ReadoutError>,
+}
+
+/// The outcome of every kernel readout.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct KernelSnapshot {
+    pub os_release: Result<String, ReadoutError>,
+    pub os_type: Result<String, ReadoutError>,
+    pub pretty_kernel: Result<String, ReadoutError>,
+}
+
+/// The outcome of every memory readout, in kilobytes.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct MemorySnapshot {
+    pub total: Result<u64, ReadoutError>,
+    pub free: Result<u64, ReadoutError>,
+    pub buffers: Result<u64, ReadoutError>,
+    pub cached: Result<u64, ReadoutError>,
+    pub reclaimable: Result<u64, ReadoutError>,
+    pub used: Result<u64, ReadoutError>,
+    pub swap_total: Result<u64, ReadoutError>,
+    pub swap_free: Result<u64, ReadoutError>,
+    pub swap_used: Result<u64, ReadoutError>,
+    pub breakdown: Result<MemoryBreakdown, ReadoutError>,
+    pub pressure: Result<SystemPressure, ReadoutError>,
+    pub vm_stats: Result<VmStats, ReadoutError>,
+    pub swap: Result<SwapInfo, ReadoutError>,
+    pub limits: Result<MemoryLimits, ReadoutError>,
+    pub numa_nodes: Result<Vec<NumaNode>, ReadoutError>,
+    pub huge_pages: Result<HugePages, ReadoutError>,
+}
+
+/// The outcome of every general readout.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GeneralSnapshot {
+    pub backlight: Result<usize, ReadoutError>,
+    pub resolution: Result<String, ReadoutError>,
+    pub username: Result<String, ReadoutError>,
+    pub hostname: Result<String, ReadoutError>,
+    pub distribution: Result<String, ReadoutError>,
+    pub desktop_environment: Result<String, ReadoutError>,
+    pub session: Result<String, ReadoutError>,
+    pub window_manager: Result<String, ReadoutError>,
+    pub terminal: Result<String, ReadoutError>,
+    /// The shell the user is currently running, see [`ShellKind::Current`].
+    pub shell: Result<String, ReadoutError>,
+    /// The shell the user has set as their default, see [`ShellKind::Default`].
+    pub default_shell: Result<String, ReadoutError>,
+    pub cpu_model_name: Result<String, ReadoutError>,
+    pub cpu_usage: Result<usize, ReadoutError>,
+    /// CPU utilisation sampled over [`CPU_STATS_INTERVAL`].
+    pub cpu_stats: Result<CpuStats, ReadoutError>,
+    pub cpu_physical_cores: Result<usize, ReadoutError>,
+    pub cpu_cores: Result<usize, ReadoutError>,
+    pub uptime: Result<usize, ReadoutError>,
+    pub machine: Result<String, ReadoutError>,
+    pub os_name: Result<String, ReadoutError>,
+    /// Used and total bytes of the filesystem the snapshot was taken for.
+    pub disk_space: Result<(u64, u64), ReadoutError>,
+    pub gpus: Result<Vec<String>, ReadoutError>,
+    pub load_average: Result<LoadAverage, ReadoutError>,
+    pub cpu_frequency: Result<CpuFrequency, ReadoutError>,
+    pub cpu_topology: Result<CpuTopology, ReadoutError>,
+    pub cpu_features: Result<CpuFeatures, ReadoutError>,
+    pub cpu_vulnerabilities: Result<Vec<CpuVulnerability>, ReadoutError>,
+    pub cpu_limits: Result<CpuLimits, ReadoutError>,
+}
+
+/// The outcome of every product readout.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ProductSnapshot {
+    pub vendor: Result<String, ReadoutError>,
+    pub family: Result<String, ReadoutError>,
+    pub product: Result<String, ReadoutError>,
+}
+
+/// The outcome of every network readout for a single interface.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct NetworkSnapshot {
+    /// The interface that was queried, if any.
+    pub interface: Option<String>,
+    pub tx_bytes: Result<usize, ReadoutError>,
+    pub tx_packets: Result<usize, ReadoutError>,
+    pub rx_bytes: Result<usize, ReadoutError>,
+    pub rx_packets: Result<usize, ReadoutError>,
+    pub logical_address: Result<String, ReadoutError>,
+    pub physical_address: Result<String, ReadoutError>,
+}
+
+/// The outcome of every sensor readout.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SensorSnapshot {
+    pub chips: Result<Vec<SensorChip>, ReadoutError>,
+    pub cpu_temperature: Result<f64, ReadoutError>,
+}
+
+/// A snapshot of the entire system, as returned by [`Readouts::snapshot`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SystemSnapshot {
+    pub battery: BatterySnapshot,
+    pub kernel: KernelSnapshot,
+    pub memory: MemorySnapshot,
+    pub general: GeneralSnapshot,
+    pub product: ProductSnapshot,
+    /// The package counts, which can only fail when collected with a
+    /// [`Collector`](crate::collect::Collector) that runs out of time.
+    pub packages: Result<Vec<(PackageManager, usize)>, ReadoutError>,
+    pub network: NetworkSnapshot,
+    pub sensors: SensorSnapshot,
+}
+
+impl Default for SystemSnapshot {
+    fn default() -> Self {
+        SystemSnapshot {
+            battery: BatterySnapshot::default(),
+            kernel: KernelSnapshot::default(),
+            memory: MemorySnapshot::default(),
+            general: GeneralSnapshot::default(),
+            product: ProductSnapshot::default(),
+            packages: Err(ReadoutError::NotImplemented),
+            network: NetworkSnapshot::default(),
+            sensors: SensorSnapshot::default(),
+        }
+    }
+}
+
+impl Default for BatterySnapshot {
+    fn default() -> Self {
+        BatterySnapshot {
+            percentage: Err(ReadoutError::NotImplemented),
+            status: Err(ReadoutError::NotImplemented),
+            ac_online: Err(ReadoutError::NotImplemented),
+            health: Err(ReadoutError::NotImplemented),
+            capacity: Err(ReadoutError::NotImplemented),
+            power: Err(ReadoutError::NotImplemented),
+            voltage: Err(ReadoutError::NotImplemented),
+            time_to_empty: Err(ReadoutError::NotImplemented),
+            time_to_full: Err(ReadoutError::NotImplemented),
+            batteries: Err(ReadoutError::NotImplemented),
+            combined_percentage: Err(ReadoutError::NotImplemented),
+            peripherals: Err(ReadoutError::NotImplemented),
+        }
+    }
+}
+
+impl Default for KernelSnapshot {
+    fn default() -> Self {
+        KernelSnapshot {
+            os_release: Err(ReadoutError::NotImplemented),
+            os_type: Err(ReadoutError::NotImplemented),
+            pretty_kernel: Err(ReadoutError::NotImplemented),
+        }
+    }
+}
+
+impl Default for MemorySnapshot {
+    fn default() -> Self {
+        MemorySnapshot {
+            total: Err(ReadoutError::NotImplemented),
+            free: Err(ReadoutError::NotImplemented),
+            buffers: Err(ReadoutError::NotImplemented),
+            cached: Err(ReadoutError::NotImplemented),
+            reclaimable: Err(ReadoutError::NotImplemented),
+            used: Err(ReadoutError::NotImplemented),
+            swap_total: Err(ReadoutError::NotImplemented),
+            swap_free: Err(ReadoutError::NotImplemented),
+            swap_used: Err(ReadoutError::NotImplemented),
+            breakdown: Err(ReadoutError::NotImplemented),
+            pressure: Err(ReadoutError::NotImplemented),
+            vm_stats: Err(ReadoutError::NotImplemented),
+            swap: Err(ReadoutError::NotImplemented),
+            limits: Err(ReadoutError::NotImplemented),
+            numa_nodes: Err(ReadoutError::NotImplemented),
+            huge_pages: Err(ReadoutError::NotImplemented),
+        }
+    }
+}
+
+impl Default for GeneralSnapshot {
+    fn default() -> Self {
+        GeneralSnapshot {
+            backlight: Err(ReadoutError::NotImplemented),
+            resolution: Err(ReadoutError::NotImplemented),
+            username: Err(ReadoutError::NotImplemented),
+            hostname: Err(ReadoutError::NotImplemented),
+            distribution: Err(ReadoutError::NotImplemented),
+            desktop_environment: Err(ReadoutError::NotImplemented),
+            session: Err(ReadoutError::NotImplemented),
+            window_manager: Err(ReadoutError::NotImplemented),
+            terminal: Err(ReadoutError::NotImplemented),
+            shell: Err(ReadoutError::NotImplemented),
+            default_shell: Err(ReadoutError::NotImplemented),
+            cpu_model_name: Err(ReadoutError::NotImplemented),
+            cpu_usage: Err(ReadoutError::NotImplemented),
+            cpu_stats: Err(ReadoutError::NotImplemented),
+            cpu_physical_cores: Err(ReadoutError::NotImplemented),
+            cpu_cores: Err(ReadoutError::NotImplemented),
+            uptime: Err(ReadoutError::NotImplemented),
+            machine: Err(ReadoutError::NotImplemented),
+            os_name: Err(ReadoutError::NotImplemented),
+            disk_space: Err(ReadoutError::NotImplemented),
+            gpus: Err(ReadoutError::NotImplemented),
+            load_average: Err(ReadoutError::NotImplemented),
+            cpu_frequency: Err(ReadoutError::NotImplemented),
+            cpu_topology: Err(ReadoutError::NotImplemented),
+            cpu_features: Err(ReadoutError::NotImplemented),
+            cpu_vulnerabilities: Err(ReadoutError::NotImplemented),
+            cpu_limits: Err(ReadoutError::NotImplemented),
+        }
+    }
+}
+
+impl Default for ProductSnapshot {
+    fn default() -> Self {
+        ProductSnapshot {
+            vendor: Err(ReadoutError::NotImplemented),
+            family: Err(ReadoutError::NotImplemented),
+            product: Err(ReadoutError::NotImplemented),
+        }
+    }
+}
+
+impl Default for NetworkSnapshot {
+    fn default() -> Self {
+        NetworkSnapshot {
+            interface: None,
+            tx_bytes: Err(ReadoutError::NotImplemented),
+            tx_packets: Err(ReadoutError::NotImplemented),
+            rx_bytes: Err(ReadoutError::NotImplemented),
+            rx_packets: Err(ReadoutError::NotImplemented),
+            logical_address: Err(ReadoutError::NotImplemented),
+            physical_address: Err(ReadoutError::NotImplemented),
+        }
+    }
+}
+
+impl Default for SensorSnapshot {
+    fn default() -> Self {
+        SensorSnapshot {
+            chips: Err(ReadoutError::NotImplemented),
+            cpu_temperature: Err(ReadoutError::NotImplemented),
+        }
+    }
+}
+
+impl Readouts {
+    /// Collects every readout into a single [`SystemSnapshot`].
+    ///
+    /// Disk space is reported for the root filesystem and network statistics are queried
+    /// without naming an interface, use [`Readouts::snapshot_with`] to choose them. This blocks
+    /// for [`CPU_STATS_INTERVAL`] while CPU utilisation is sampled.
+    pub fn snapshot(&self) -> SystemSnapshot {
+        #[cfg(target_os = "windows")]
+        let path = Path::new("C:\\");
+        #[cfg(not(target_os = "windows"))]
+        let path = Path::new("/");
+
+        self.snapshot_with(None, path)
+    }
+
+    /// Collects every readout into a single [`SystemSnapshot`], querying network statistics
+    /// for `interface` and disk space for the filesystem that holds `path`.
+    pub fn snapshot_with(&self, interface: Option<&str>, path: &Path) -> SystemSnapshot {
+        SystemSnapshot {
+            battery: BatterySnapshot {
+                percentage: self.battery.percentage(),
+                status: self.battery.status(),
+                ac_online: self.battery.ac_online(),
+                health: self.battery.health(),
+                capacity: self.battery.capacity(),
+                power: self.battery.power(),
+                voltage: self.battery.voltage(),
+                time_to_empty: self.battery.time_to_empty(),
+                time_to_full: self.battery.time_to_full(),
+                batteries: self.battery.batteries(),
+                combined_percentage: self.battery.combined_percentage(),
+                peripherals: self.battery.peripherals(),
+            },
+            kernel: KernelSnapshot {
+                os_release: self.kernel.os_release(),
+                os_type: self.kernel.os_type(),
+                pretty_kernel: self.kernel.pretty_kernel(),
+            },
+            memory: MemorySnapshot {
+                total: self.memory.total(),
+                free: self.memory.free(),
+                buffers: self.memory.buffers(),
+                cached: self.memory.cached(),
+                reclaimable: self.memory.reclaimable(),
+                used: self.memory.used(),
+                swap_total: self.memory.swap_total(),
+                swap_free: self.memory.swap_free(),
+                swap_used: self.memory.swap_used(),
+                breakdown: self.memory.breakdown(),
+                pressure: self.memory.pressure(),
+                vm_stats: self.memory.vm_stats(),
+                swap: self.memory.swap(),
+                limits: self.memory.limits(),
+                numa_nodes: self.memory.numa_nodes(),
+                huge_pages: self.memory.huge_pages(),
+            },
+            general: GeneralSnapshot {
+                backlight: self.general.backlight(),
+                resolution: self.general.resolution(),
+                username: self.general.username(),
+                hostname: self.general.hostname(),
+                distribution: self.general.distribution(),
+                desktop_environment: self.general.desktop_environment(),
+                session: self.general.session(),
+                window_manager: self.general.window_manager(),
+                terminal: self.general.terminal(),
+                shell: self
+                    .general
+                    .shell(ShellFormat::Relative, ShellKind::Current),
+                default_shell: self
+                    .general
+                    .shell(ShellFormat::Relative, ShellKind::Default),
+                cpu_model_name: self.general.cpu_model_name(),
+                cpu_usage: self.general.cpu_usage(),
+                cpu_stats: self.general.cpu_stats(CPU_STATS_INTERVAL),
+                cpu_physical_cores: self.general.cpu_physical_cores(),
+                cpu_cores: self.general.cpu_cores(),
+                uptime: self.general.uptime(),
+                machine: self.general.machine(),
+                os_name: self.general.os_name(),
+                disk_space: self.general.disk_space(path),
+                gpus: self.general.gpus(),
+                load_average: self.general.load_average(),
+                cpu_frequency: self.general.cpu_frequency(),
+                cpu_topology: self.general.cpu_topology(),
+                cpu_features: self.general.cpu_features(),
+                cpu_vulnerabilities: self.general.cpu_vulnerabilities(),
+                cpu_limits: self.general.cpu_limits(),
+            },
+            product: ProductSnapshot {
+                vendor: self.product.vendor(),
+                family: self.product.family(),
+                product: self.product.product(),
+            },
+            packages: Ok(self.packages.count_pkgs()),
+            network: NetworkSnapshot {
+                interface: interface.map(String::from),
+                tx_bytes: self.network.tx_bytes(interface),
+                tx_packets: self.network.tx_packets(interface),
+                rx_bytes: self.network.rx_bytes(interface),
+                rx_packets: self.network.rx_packets(interface),
+                logical_address: self.network.logical_address(interface),
+                physical_address: self.network.physical_address(interface),
+            },
+            sensors: SensorSnapshot {
+                chips: self.sensors.chips(),
+                cpu_temperature: self.sensors.cpu_temperature(),
+            },
+        }
+    }
+}
@@ -2,10 +2,16 @@
 //! different readouts from various operating systems. For each operating system, there must be an implementation of these traits.
 #![allow(unused_variables)]
 
-use std::path::Path;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
 /// This enum contains possible error types when doing sensor & variable readouts.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ReadoutError {
     /// A specific metric might not be available on all systems (e. g. battery percentage on a
     /// desktop). \
@@ -22,6 +28,158 @@ pub enum ReadoutError {
     /// Getting a readout on a specific operating system might not make sense or causes some other
     /// kind of warning. This is not necessarily an error.
     Warning(String),
+
+    /// A readout for a metric was attempted but failed. Unlike `Other`, this carries the
+    /// [kind](ReadoutErrorKind) of failure, the metric, path or command that was involved and
+    /// the underlying error, which is exposed through [`std::error::Error::source`].
+    Failed(Box<ReadoutFailure>),
+}
+
+/// Describes why a readout failed, see [`ReadoutError::Failed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum ReadoutErrorKind {
+    /// A file, directory or program that the readout relies on does not exist.
+    NotFound,
+
+    /// A file, directory or program exists, but the current user may not access it.
+    PermissionDenied,
+
+    /// The data that was read could not be understood.
+    ParseError,
+
+    /// The readout did not complete in time.
+    Timeout,
+
+    /// The system lacks a runtime prerequisite of the readout, e.g. a library or a program.
+    Unsupported,
+
+    /// Any other I/O failure.
+    Io,
+
+    /// Any failure that does not fit into one of the other kinds.
+    Other,
+}
+
+impl std::fmt::Display for ReadoutErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadoutErrorKind::NotFound => write!(f, "not found"),
+            ReadoutErrorKind::PermissionDenied => write!(f, "permission denied"),
+            ReadoutErrorKind::ParseError => write!(f, "could not parse value"),
+            ReadoutErrorKind::Timeout => write!(f, "timed out"),
+            ReadoutErrorKind::Unsupported => write!(f, "unsupported on this system"),
+            ReadoutErrorKind::Io => write!(f, "I/O error"),
+            ReadoutErrorKind::Other => write!(f, "readout failed"),
+        }
+    }
+}
+
+/// The details of a [`ReadoutError::Failed`] error.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReadoutFailure {
+    /// Why the readout failed.
+    pub kind: ReadoutErrorKind,
+
+    /// The metric that was being read, e.g. `percentage`.
+    pub metric: Option<String>,
+
+    /// The path that was accessed, e.g. `/sys/class/dmi/id/product_serial`.
+    pub path: Option<PathBuf>,
+
+    /// The command that was run, e.g. `xbps-query -l`.
+    pub command: Option<String>,
+
+    /// A human-readable description of the failure.
+    pub message: Option<String>,
+
+    /// The underlying error, if any.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub source: Option<Arc<dyn std::error::Error + Send + Sync>>,
+}
+
+impl ReadoutError {
+    /// Creates a [`ReadoutError::Failed`] error of the given kind.
+    pub fn failed(kind: ReadoutErrorKind) -> Self {
+        ReadoutError::Failed(Box::new(ReadoutFailure {
+            kind,
+            metric: None,
+            path: None,
+            command: None,
+            message: None,
+            source: None,
+        }))
+    }
+
+    /// Creates a [`ReadoutError::Failed`] error of the given kind, caused by `source`.
+    pub fn from_source<E>(kind: ReadoutErrorKind, source: E) -> Self
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let mut error = ReadoutError::failed(kind);
+        if let ReadoutError::Failed(failure) = &mut error {
+            failure.source = Some(Arc::new(source));
+        }
+
+        error
+    }
+
+    /// Returns the kind of failure, or `None` for errors that do not describe a failed attempt,
+    /// i.e. `MetricNotAvailable`, `NotImplemented` and `Warning`.
+    pub fn kind(&self) -> Option<ReadoutErrorKind> {
+        match self {
+            ReadoutError::Failed(failure) => Some(failure.kind),
+            ReadoutError::Other(_) => Some(ReadoutErrorKind::Other),
+            _ => None,
+        }
+    }
+
+    /// Returns the details of a [`ReadoutError::Failed`] error.
+    pub fn failure(&self) -> Option<&ReadoutFailure> {
+        match self {
+            ReadoutError::Failed(failure) => Some(failure),
+            _ => None,
+        }
+    }
+
+    /// Records the metric that was being read. \
+    /// `Other` errors are turned into `Failed` ones, `MetricNotAvailable`, `NotImplemented` and
+    /// `Warning` are returned unchanged.
+    pub fn with_metric<S: Into<String>>(self, metric: S) -> Self {
+        self.map_failure(|failure| failure.metric = Some(metric.into()))
+    }
+
+    /// Records the path that was accessed, see [`ReadoutError::with_metric`].
+    pub fn with_path<P: AsRef<Path>>(self, path: P) -> Self {
+        self.map_failure(|failure| failure.path = Some(path.as_ref().to_path_buf()))
+    }
+
+    /// Records the command that was run, see [`ReadoutError::with_metric`].
+    pub fn with_command<S: Into<String>>(self, command: S) -> Self {
+        self.map_failure(|failure| failure.command = Some(command.into()))
+    }
+
+    /// Records a human-readable description of the failure, see [`ReadoutError::with_metric`].
+    pub fn with_message<S: Into<String>>(self, message: S) -> Self {
+        self.map_failure(|failure| failure.message = Some(message.into()))
+    }
+
+    fn map_failure<F: FnOnce(&mut ReadoutFailure)>(self, f: F) -> Self {
+        let mut error = match self {
+            ReadoutError::Other(message) => {
+                ReadoutError::failed(ReadoutErrorKind::Other).with_message(message)
+            }
+            error => error,
+        };
+
+        if let ReadoutError::Failed(failure) = &mut error {
+            f(failure);
+        }
+
+        error
+    }
 }
 
 impl std::fmt::Display for ReadoutError {
@@ -35,6 +193,38 @@ impl std::fmt::Display for ReadoutError {
             }
             ReadoutError::Other(s) => write!(f, "{}", s),
             ReadoutError::Warning(s) => write!(f, "{}", s),
+            ReadoutError::Failed(failure) => {
+                if let Some(metric) = &failure.metric {
+                    write!(f, "{metric}: ")?;
+                }
+
+                match &failure.message {
+                    Some(message) => write!(f, "{message}")?,
+                    None => write!(f, "{}", failure.kind)?,
+                }
+
+                if let Some(path) = &failure.path {
+                    write!(f, " ({})", path.display())?;
+                }
+
+                if let Some(command) = &failure.command {
+                    write!(f, " (running \"{command}\")")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReadoutError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReadoutError::Failed(failure) => failure
+                .source
+                .as_deref()
+                .map(|e| e as &(dyn std::error::Error + 'static)),
+            _ => None,
         }
     }
 }
@@ -91,13 +281,69 @@ pub trait BatteryReadout {
     /// a u8 in the range of `0` to `100`.
     fn percentage(&self) -> Result<u8, ReadoutError>;
 
-    /// This function is used for querying the current battery charging state. If the battery is
-    /// currently being charged, we expect a return value of `BatteryState::Charging`, otherwise
-    /// `BatteryState::Discharging`.
+    /// This function is used for querying the current battery charging state, _e.g._
+    /// `BatteryState::Charging` if the battery is currently being charged.
     fn status(&self) -> Result<BatteryState, ReadoutError>;
 
+    /// This function is used for querying whether the host is connected to an external power
+    /// source, such as an AC adapter or a USB charger.
+    fn ac_online(&self) -> Result<bool, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
     /// This function is used for querying the current battery's health in percentage.
     fn health(&self) -> Result<u8, ReadoutError>;
+
+    /// This function is used for querying the current and design capacity of the current
+    /// battery, from which its [health](BatteryCapacity::health) is derived.
+    fn capacity(&self) -> Result<BatteryCapacity, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function is used for querying the rate at which the current battery is being
+    /// charged or discharged, in watts.
+    fn power(&self) -> Result<f64, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function is used for querying the current voltage of the current battery, in volts.
+    fn voltage(&self) -> Result<f64, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function is used for estimating how long the current battery lasts at its current
+    /// rate of discharge. Fails with `MetricNotAvailable` while the battery isn't discharging.
+    fn time_to_empty(&self) -> Result<Duration, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function is used for estimating how long the current battery takes to be fully
+    /// charged at its current rate of charge. Fails with `MetricNotAvailable` while the battery
+    /// isn't charging.
+    fn time_to_full(&self) -> Result<Duration, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function is used for querying every battery of the host. The batteries should be
+    /// ordered by name, and the first one should be the battery described by
+    /// [`percentage`](BatteryReadout::percentage), [`status`](BatteryReadout::status) and
+    /// [`health`](BatteryReadout::health).
+    fn batteries(&self) -> Result<Vec<Battery>, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function is used for querying the batteries of peripherals, such as wireless mice,
+    /// keyboards, headsets or game controllers. These are never returned by
+    /// [`batteries`](BatteryReadout::batteries) or described by the other battery readouts.
+    fn peripherals(&self) -> Result<Vec<PeripheralBattery>, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function is used for querying the charge of all batteries combined in percentage,
+    /// see [`Battery::combined_percentage`].
+    fn combined_percentage(&self) -> Result<u8, ReadoutError> {
+        Battery::combined_percentage(&self.batteries()?).ok_or(ReadoutError::MetricNotAvailable)
+    }
 }
 
 /**
@@ -248,6 +494,48 @@ pub trait MemoryReadout {
 
     /// This function should return the amount of currently used swap in kilobytes.
     fn swap_used(&self) -> Result<u64, ReadoutError>;
+
+    /// This function should return everything the kernel reports about the use of memory,
+    /// read at once so that the values are consistent with each other.
+    fn breakdown(&self) -> Result<MemoryBreakdown, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return how long tasks were stalled waiting for the CPU, memory and
+    /// I/O, which tells a busy system apart from one that is thrashing.
+    fn pressure(&self) -> Result<SystemPressure, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return counters of virtual memory events since boot, _e.g._ page
+    /// faults and OOM kills.
+    fn vm_stats(&self) -> Result<VmStats, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the swap devices in use, along with the state of compressed
+    /// swap in memory such as zram and zswap.
+    fn swap(&self) -> Result<SwapInfo, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the memory available to the calling process, which is
+    /// limited by its cgroup when it runs in a container.
+    fn limits(&self) -> Result<MemoryLimits, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the memory and CPUs of each NUMA node, along with the
+    /// distances between the nodes.
+    fn numa_nodes(&self) -> Result<Vec<NumaNode>, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the pools of huge pages of each size, and whether the
+    /// kernel backs memory with huge pages transparently.
+    fn huge_pages(&self) -> Result<HugePages, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
 }
 
 /**
@@ -584,8 +872,54 @@ pub trait GeneralReadout {
     fn cpu_model_name(&self) -> Result<String, ReadoutError>;
 
     /// This function should return the average CPU usage over the last minute.
+    ///
+    /// Some platforms derive this from the load average, which can exceed 100% and reacts slowly,
+    /// use [`GeneralReadout::cpu_stats`] to measure the actual utilisation.
     fn cpu_usage(&self) -> Result<usize, ReadoutError>;
 
+    /// This function should return the CPU utilisation, measured by sampling the time spent by
+    /// each CPU at the start and the end of `interval`. \
+    /// This blocks the calling thread for `interval`.
+    fn cpu_stats(&self, interval: Duration) -> Result<CpuStats, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the system load averaged over the last 1, 5 and 15 minutes,
+    /// along with the number of tasks where the platform provides it.
+    fn load_average(&self) -> Result<LoadAverage, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the frequency of each logical CPU, along with the driver and
+    /// policy that scale it.
+    fn cpu_frequency(&self) -> Result<CpuFrequency, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return how the logical CPUs are grouped into cores, dies, sockets
+    /// and NUMA nodes, and the caches they share.
+    fn cpu_topology(&self) -> Result<CpuTopology, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the instruction set extensions supported by every logical
+    /// CPU, _e.g._ `avx2` or `aes`.
+    fn cpu_features(&self) -> Result<CpuFeatures, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return whether the CPU is affected by each hardware vulnerability
+    /// known to the kernel, _e.g._ Spectre or Meltdown, and how it is mitigated.
+    fn cpu_vulnerabilities(&self) -> Result<Vec<CpuVulnerability>, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the CPU time available to the calling process, which is
+    /// limited by its cgroup when it runs in a container.
+    fn cpu_limits(&self) -> Result<CpuLimits, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
     /// This function should return the number of physical cores of the host's processor.
     fn cpu_physical_cores(&self) -> Result<usize, ReadoutError>;
 
@@ -615,22 +949,70 @@ pub trait GeneralReadout {
     fn gpus(&self) -> Result<Vec<String>, ReadoutError>;
 }
 
+/**
+This trait provides the interface for implementing functionality used for reading the _hardware
+sensors_ of the host machine, such as temperatures, fan speeds and voltages.
+
+# Example
+
+```
+use libmacchina::traits::{SensorChip, SensorReadout};
+use libmacchina::traits::ReadoutError;
+
+pub struct LinuxSensorReadout;
+
+impl SensorReadout for LinuxSensorReadout {
+    fn new() -> Self {
+        LinuxSensorReadout {}
+    }
+
+    fn chips(&self) -> Result<Vec<SensorChip>, ReadoutError> {
+        Ok(vec![SensorChip {
+            name: String::from("k10temp"),
+            sensors: vec![],
+        }])
+    }
+
+    fn cpu_temperature(&self) -> Result<f64, ReadoutError> {
+        Ok(48.5)
+    }
+}
+```
+*/
+pub trait SensorReadout {
+    /// Creates a new instance of the structure which implements this trait.
+    fn new() -> Self;
+
+    /// This function should return every sensor of the host, grouped by the chip that provides
+    /// them.
+    fn chips(&self) -> Result<Vec<SensorChip>, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+
+    /// This function should return the temperature of the CPU package in degrees Celsius.
+    fn cpu_temperature(&self) -> Result<f64, ReadoutError> {
+        Err(ReadoutError::NotImplemented)
+    }
+}
+
 /// Holds the possible variants for battery status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BatteryState {
     Charging,
     Discharging,
+    /// The battery is fully charged.
+    Full,
+    /// The battery is connected to a power source but isn't charging, _e.g._ because a charge
+    /// threshold was reached.
+    NotCharging,
+    /// The platform doesn't know what the battery is doing.
+    Unknown,
 }
 
 impl std::fmt::Display for BatteryState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match *self {
-            BatteryState::Charging => {
-                write!(f, "Charging")
-            }
-            BatteryState::Discharging => {
-                write!(f, "Discharging")
-            }
-        }
+        write!(f, "{}", <&'static str>::from(*self))
     }
 }
 
@@ -639,19 +1021,206 @@ impl From<BatteryState> for &'static str {
         match state {
             BatteryState::Charging => "Charging",
             BatteryState::Discharging => "Discharging",
+            BatteryState::Full => "Full",
+            BatteryState::NotCharging => "Not charging",
+            BatteryState::Unknown => "Unknown",
         }
     }
 }
 
+/// A single battery, as returned by [`BatteryReadout::batteries`]. Properties that the battery
+/// doesn't report are `None`.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Battery {
+    /// The name the platform knows the battery by, _e.g._ `BAT0`.
+    pub name: String,
+    pub percentage: Option<u8>,
+    pub state: Option<BatteryState>,
+    pub health: Option<u8>,
+
+    /// The chemistry of the battery, _e.g._ `Li-ion`.
+    pub technology: Option<String>,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+
+    /// The energy the battery holds when fully charged, in watt-hours.
+    pub energy_full: Option<f64>,
+
+    pub capacity: Option<BatteryCapacity>,
+
+    /// The rate at which the battery is being charged or discharged, in watts.
+    pub power: Option<f64>,
+
+    /// The current voltage of the battery, in volts.
+    pub voltage: Option<f64>,
+
+    pub time_to_empty: Option<Duration>,
+    pub time_to_full: Option<Duration>,
+}
+
+impl Battery {
+    /// Returns the charge of `batteries` combined in percentage, weighing each battery by
+    /// [`energy_full`](Battery::energy_full). When the capacity of any battery is unknown, every
+    /// battery is weighed equally. \
+    /// Batteries whose percentage is unknown are left out.
+    pub fn combined_percentage(batteries: &[Battery]) -> Option<u8> {
+        let charged: Vec<&Battery> = batteries
+            .iter()
+            .filter(|b| b.percentage.is_some())
+            .collect();
+        if charged.is_empty() {
+            return None;
+        }
+
+        let weighted = charged
+            .iter()
+            .all(|b| b.energy_full.is_some_and(|e| e > 0.0));
+        let weight = |b: &Battery| {
+            if weighted {
+                b.energy_full.unwrap()
+            } else {
+                1.0
+            }
+        };
+
+        let total: f64 = charged.iter().map(|b| weight(b)).sum();
+        let charge: f64 = charged
+            .iter()
+            .map(|b| weight(b) * b.percentage.unwrap() as f64)
+            .sum();
+
+        Some((charge / total).round() as u8)
+    }
+}
+
+/// The unit a battery reports its capacity in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CapacityUnit {
+    WattHours,
+    AmpereHours,
+}
+
+/// The capacity of a battery, as returned by [`BatteryReadout::capacity`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BatteryCapacity {
+    /// The unit of `full` and `full_design`, which depends on whether the battery reports its
+    /// energy or its charge.
+    pub unit: CapacityUnit,
+
+    /// What the battery holds when fully charged.
+    pub full: f64,
+
+    /// What the battery held when fully charged as it left the factory.
+    pub full_design: f64,
+
+    /// The number of charge cycles the battery went through, if it reports it.
+    pub cycle_count: Option<u64>,
+}
+
+impl BatteryCapacity {
+    /// Returns how much of its design capacity the battery still holds in percentage, capped
+    /// at `100`. Returns `None` if the design capacity is unknown, i.e. zero.
+    pub fn health(&self) -> Option<u8> {
+        if self.full_design <= 0.0 {
+            return None;
+        }
+
+        Some((self.full / self.full_design * 100.0).round().min(100.0) as u8)
+    }
+}
+
+/// A coarse charge level, reported by batteries that don't know their exact percentage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CapacityLevel {
+    Critical,
+    Low,
+    Normal,
+    High,
+    Full,
+    Unknown,
+}
+
+/// The kind of device a [`PeripheralBattery`] powers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PeripheralKind {
+    Mouse,
+    Keyboard,
+    Headset,
+    Controller,
+    Tablet,
+    Unknown,
+}
+
+/// The battery of a peripheral, as returned by [`BatteryReadout::peripherals`]. Properties
+/// that the device doesn't report are `None`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PeripheralBattery {
+    /// The name the platform knows the battery by, _e.g._ `hidpp_battery_0`.
+    pub name: String,
+    pub kind: PeripheralKind,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    pub percentage: Option<u8>,
+
+    /// The charge level of devices that don't report a percentage.
+    pub capacity_level: Option<CapacityLevel>,
+    pub state: Option<BatteryState>,
+}
+
+/// What a [`Sensor`] measures, which determines the unit of its values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SensorKind {
+    /// In degrees Celsius.
+    Temperature,
+    /// In revolutions per minute.
+    Fan,
+    /// In volts.
+    Voltage,
+}
+
+/// A single hardware sensor, as returned by [`SensorReadout::chips`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sensor {
+    /// The label of the sensor, _e.g._ `Tctl` or `Package id 0`.
+    pub label: String,
+    pub kind: SensorKind,
+    pub value: f64,
+
+    /// The highest value the sensor should report during normal operation.
+    pub max: Option<f64>,
+
+    /// The value at which the hardware takes action, _e.g._ by shutting down.
+    pub critical: Option<f64>,
+}
+
+/// A chip providing a set of sensors, _e.g._ `k10temp`, `coretemp`, `amdgpu`, `nvme` or
+/// `acpitz`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SensorChip {
+    pub name: String,
+    pub sensors: Vec<Sensor>,
+}
+
 /// The currently running shell is a program, whose path
 /// can be _relative_, or _absolute_.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ShellFormat {
     Relative,
     Absolute,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 /// There are two distinct kinds of shells, a so called *"current"* shell, i.e. the shell the user is currently using.
 /// And a default shell, i.e. that the user sets for themselves using the `chsh` tool.
 pub enum ShellKind {
@@ -660,6 +1229,8 @@ pub enum ShellKind {
 }
 
 /// The supported package managers whose packages can be extracted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PackageManager {
     Homebrew,
     MacPorts,
@@ -709,3 +1280,609 @@ impl std::fmt::Display for PackageManager {
         }
     }
 }
+
+/// How the time of one or more CPUs was spent over a sampling interval, in percent. \
+/// Time spent running guests is accounted for as `user` and `nice` time.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuTimes {
+    pub user: f64,
+    pub nice: f64,
+    pub system: f64,
+    pub idle: f64,
+    pub iowait: f64,
+    pub irq: f64,
+    pub softirq: f64,
+    pub steal: f64,
+}
+
+impl CpuTimes {
+    /// Returns the percentage of time the CPU was busy, i.e. neither idle nor waiting for I/O.
+    pub fn busy(&self) -> f64 {
+        self.user + self.nice + self.system + self.irq + self.softirq + self.steal
+    }
+}
+
+/// CPU utilisation sampled over an interval, as returned by [`GeneralReadout::cpu_stats`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuStats {
+    /// The utilisation of all CPUs combined.
+    pub total: CpuTimes,
+
+    /// The utilisation of each online logical CPU, along with its number.
+    pub cores: Vec<(usize, CpuTimes)>,
+
+    /// The number of context switches since boot, at the end of the interval.
+    pub context_switches: u64,
+
+    /// The number of interrupts serviced since boot, at the end of the interval.
+    pub interrupts: u64,
+}
+
+/// The frequency of a single logical CPU, in MHz.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CoreFrequency {
+    /// The number of the logical CPU.
+    pub cpu: usize,
+
+    /// The frequency the CPU is currently running at.
+    pub current: Option<f64>,
+
+    /// The lowest frequency the hardware supports.
+    pub min: Option<f64>,
+
+    /// The highest frequency the hardware supports, including boost.
+    pub max: Option<f64>,
+}
+
+/// The frequency of every logical CPU and how it is scaled, as returned by
+/// [`GeneralReadout::cpu_frequency`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuFrequency {
+    pub cores: Vec<CoreFrequency>,
+
+    /// The driver scaling the frequency, _e.g._ `intel_pstate` or `acpi-cpufreq`.
+    pub driver: Option<String>,
+
+    /// The scaling governor, _e.g._ `powersave` or `schedutil`.
+    pub governor: Option<String>,
+
+    /// The energy-performance preference passed to the hardware, _e.g._ `balance_performance`.
+    pub energy_performance_preference: Option<String>,
+
+    /// Whether the CPU may run above its base frequency, if the driver reports it.
+    pub boost: Option<bool>,
+}
+
+impl CpuFrequency {
+    /// Returns the highest frequency any CPU supports, or, failing that, the highest frequency
+    /// any CPU is currently running at.
+    pub fn max(&self) -> Option<f64> {
+        let highest =
+            |f: fn(&CoreFrequency) -> Option<f64>| self.cores.iter().filter_map(f).reduce(f64::max);
+
+        highest(|core| core.max).or_else(|| highest(|core| core.current))
+    }
+}
+
+/// The kind of data a CPU cache holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CacheKind {
+    Data,
+    Instruction,
+    Unified,
+}
+
+/// A level of the CPU cache hierarchy, _e.g._ the L1 data caches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuCache {
+    pub level: u8,
+    pub kind: CacheKind,
+
+    /// The size of a single instance of the cache, in bytes.
+    pub size: u64,
+
+    /// The number of instances of the cache, _e.g._ one L1 cache per core.
+    pub instances: usize,
+
+    /// The number of logical CPUs sharing each instance.
+    pub shared_by: usize,
+}
+
+impl CpuCache {
+    /// Returns the combined size of every instance of the cache, in bytes.
+    pub fn total_size(&self) -> u64 {
+        self.size * self.instances as u64
+    }
+}
+
+/// Whether the cores of a hybrid CPU are tuned for performance or for efficiency, _e.g._ Intel's
+/// P-cores and E-cores or ARM's big and LITTLE cores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CoreType {
+    Performance,
+    Efficiency,
+}
+
+/// A group of identical cores of a hybrid CPU.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuCluster {
+    pub core_type: CoreType,
+
+    /// The logical CPUs belonging to the cluster.
+    pub cpus: Vec<usize>,
+
+    /// The number of physical cores in the cluster.
+    pub cores: usize,
+
+    /// The model of the cores, if it can be told apart from the other clusters.
+    pub model: Option<String>,
+
+    /// The lowest frequency the cores support, in MHz.
+    pub min_frequency: Option<f64>,
+
+    /// The highest frequency the cores support, in MHz.
+    pub max_frequency: Option<f64>,
+}
+
+/// How the logical CPUs are laid out, as returned by [`GeneralReadout::cpu_topology`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuTopology {
+    /// The number of physical packages, i.e. populated sockets.
+    pub sockets: usize,
+
+    /// The number of dies across all sockets.
+    pub dies: usize,
+
+    /// The number of physical cores across all sockets.
+    pub cores: usize,
+
+    /// The number of logical CPUs that are online, i.e. hardware threads.
+    pub threads: usize,
+
+    /// The number of logical CPUs the system could bring online, including offline and
+    /// hot-pluggable ones.
+    pub possible: usize,
+
+    /// The number of NUMA nodes.
+    pub numa_nodes: usize,
+
+    /// The caches, ordered by level.
+    pub caches: Vec<CpuCache>,
+
+    /// The clusters of a hybrid CPU, ordered from the fastest to the slowest. This is empty if
+    /// all cores are alike.
+    pub clusters: Vec<CpuCluster>,
+}
+
+impl CpuTopology {
+    /// Returns the number of performance and efficiency cores of a hybrid CPU, _e.g._ `6P + 8E`,
+    /// or `None` if all cores are alike.
+    pub fn hybrid_summary(&self) -> Option<String> {
+        if self.clusters.is_empty() {
+            return None;
+        }
+
+        let count = |core_type: CoreType| -> usize {
+            self.clusters
+                .iter()
+                .filter(|cluster| cluster.core_type == core_type)
+                .map(|cluster| cluster.cores)
+                .sum()
+        };
+
+        Some(format!(
+            "{}P + {}E",
+            count(CoreType::Performance),
+            count(CoreType::Efficiency)
+        ))
+    }
+}
+
+/// The instruction set extensions supported by a CPU, as returned by
+/// [`GeneralReadout::cpu_features`]. Extensions are named as the kernel names them, _e.g._
+/// `sse4_2` on x86, `asimd` on ARM or `zicsr` on RISC-V.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuFeatures {
+    pub flags: BTreeSet<String>,
+}
+
+impl CpuFeatures {
+    /// Returns `true` if the CPU supports the extension named `flag`.
+    pub fn contains(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+
+    /// Returns the highest x86-64 micro-architecture level, from 1 to 4, whose extensions the
+    /// CPU supports, or `None` if it isn't a 64-bit x86 CPU.
+    pub fn x86_64_level(&self) -> Option<u8> {
+        const LEVELS: &[&[&str]] = &[
+            &[
+                "cmov", "cx8", "fpu", "fxsr", "mmx", "syscall", "sse", "sse2",
+            ],
+            &[
+                "cx16", "lahf_lm", "popcnt", "pni", "sse4_1", "sse4_2", "ssse3",
+            ],
+            &[
+                "abm", "avx", "avx2", "bmi1", "bmi2", "f16c", "fma", "movbe", "xsave",
+            ],
+            &["avx512f", "avx512bw", "avx512cd", "avx512dq", "avx512vl"],
+        ];
+
+        if !self.contains("lm") {
+            return None;
+        }
+
+        let level = LEVELS
+            .iter()
+            .take_while(|flags| flags.iter().all(|flag| self.contains(flag)))
+            .count();
+
+        u8::try_from(level).ok().filter(|level| *level > 0)
+    }
+}
+
+/// Whether a CPU is affected by a hardware vulnerability.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VulnerabilityStatus {
+    NotAffected,
+
+    /// The CPU is affected but protected, by the given mitigation.
+    Mitigated(String),
+
+    /// The CPU is affected and unprotected, with details where the kernel gives them.
+    Vulnerable(Option<String>),
+
+    /// The kernel can't tell, _e.g._ because it runs under a hypervisor.
+    Unknown(String),
+}
+
+/// A hardware vulnerability, as returned by [`GeneralReadout::cpu_vulnerabilities`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuVulnerability {
+    /// The kernel's name for the vulnerability, _e.g._ `spectre_v2` or `retbleed`.
+    pub name: String,
+    pub status: VulnerabilityStatus,
+}
+
+/// The system load, i.e. the number of tasks that are running or waiting to run, averaged over
+/// several periods, as returned by [`GeneralReadout::load_average`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+
+    /// The number of tasks that are currently runnable, if the platform reports it.
+    pub runnable_tasks: Option<u64>,
+
+    /// The number of tasks that currently exist, if the platform reports it.
+    pub total_tasks: Option<u64>,
+}
+
+/// How memory is used, as returned by [`MemoryReadout::breakdown`]. Every amount is in
+/// kilobytes, and is `None` if the kernel doesn't report it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MemoryBreakdown {
+    pub total: u64,
+    pub free: u64,
+
+    /// An estimate of how much memory can be allocated without swapping, which unlike `free`
+    /// includes caches that can be dropped.
+    pub available: Option<u64>,
+
+    pub buffers: u64,
+
+    /// The page cache, excluding the swap cache.
+    pub cached: u64,
+
+    /// Memory used by `tmpfs` and shared memory segments, which counts towards `cached`.
+    pub shared: Option<u64>,
+
+    /// Memory that was used recently and is unlikely to be reclaimed.
+    pub active: Option<u64>,
+
+    /// Memory that is a candidate for being reclaimed.
+    pub inactive: Option<u64>,
+
+    /// Memory waiting to be written back to disk.
+    pub dirty: Option<u64>,
+
+    /// Memory that is being written back to disk.
+    pub writeback: Option<u64>,
+
+    /// The kernel's slab caches, split into the parts that can and can't be reclaimed.
+    pub slab_reclaimable: Option<u64>,
+    pub slab_unreclaimable: Option<u64>,
+
+    /// Memory used by page tables.
+    pub page_tables: Option<u64>,
+
+    /// The amount of memory allocated by processes, including memory that isn't backed by
+    /// pages yet, and the limit the kernel enforces when overcommitting is disabled.
+    pub committed: Option<u64>,
+    pub commit_limit: Option<u64>,
+
+    pub swap_total: u64,
+    pub swap_free: u64,
+
+    /// Swapped out memory that is also still in memory.
+    pub swap_cached: Option<u64>,
+
+    /// The number of huge pages in the pool and how many of them are free.
+    pub huge_pages_total: Option<u64>,
+    pub huge_pages_free: Option<u64>,
+
+    /// The size of a huge page.
+    pub huge_page_size: Option<u64>,
+
+    /// The memory used by the zswap pool, and the amount of memory compressed into it.
+    pub zswap: Option<u64>,
+    pub zswapped: Option<u64>,
+}
+
+impl MemoryBreakdown {
+    /// Returns the amount of memory in use, i.e. that can't be reclaimed without swapping. This
+    /// is `total - available`, or an approximation on kernels that don't estimate `available`.
+    pub fn used(&self) -> u64 {
+        match self.available {
+            Some(available) => self.total.saturating_sub(available),
+            None => self.total.saturating_sub(
+                self.free + self.buffers + self.cached + self.slab_reclaimable.unwrap_or(0),
+            ),
+        }
+    }
+
+    /// Returns the amount of swap in use.
+    pub fn swap_used(&self) -> u64 {
+        self.swap_total.saturating_sub(self.swap_free)
+    }
+}
+
+/// The share of time in which tasks were stalled on a resource, in percent, averaged over the
+/// last 10, 60 and 300 seconds, and the total time they were stalled since boot.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PressureStall {
+    pub avg10: f64,
+    pub avg60: f64,
+    pub avg300: f64,
+    pub total: Duration,
+}
+
+/// The pressure on a resource, as reported by Linux's pressure stall information.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pressure {
+    /// The time in which at least one task was stalled.
+    pub some: PressureStall,
+
+    /// The time in which all non-idle tasks were stalled at once, if the kernel reports it.
+    pub full: Option<PressureStall>,
+}
+
+/// The pressure on the CPU, memory and I/O, as returned by [`MemoryReadout::pressure`]. A
+/// resource is `None` if the kernel doesn't track it.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SystemPressure {
+    pub cpu: Option<Pressure>,
+    pub memory: Option<Pressure>,
+    pub io: Option<Pressure>,
+}
+
+/// Whether swap space lives on a block device or in a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SwapKind {
+    Partition,
+    File,
+}
+
+/// A swap device or file. Sizes are in kilobytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SwapDevice {
+    pub path: PathBuf,
+    pub kind: SwapKind,
+    pub size: u64,
+    pub used: u64,
+
+    /// Devices with a higher priority are used first.
+    pub priority: i32,
+}
+
+/// A zram device, i.e. a compressed block device in memory that is usually used for swap.
+/// Sizes are in kilobytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZramDevice {
+    /// The name of the device, _e.g._ `zram0`.
+    pub name: String,
+
+    /// The compression algorithm, _e.g._ `zstd`.
+    pub algorithm: Option<String>,
+
+    /// The size of the device as seen by its users.
+    pub disk_size: u64,
+
+    /// The amount of data stored, before and after compression.
+    pub original: u64,
+    pub compressed: u64,
+
+    /// The memory used by the device, including fragmentation and metadata.
+    pub memory_used: u64,
+}
+
+impl ZramDevice {
+    /// Returns how many times smaller the data became through compression, or `None` if the
+    /// device is empty.
+    pub fn compression_ratio(&self) -> Option<f64> {
+        if self.compressed == 0 {
+            return None;
+        }
+
+        Some(self.original as f64 / self.compressed as f64)
+    }
+}
+
+/// The state of zswap, a compressed cache in memory for pages that are being swapped out.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Zswap {
+    pub enabled: bool,
+
+    /// The compression algorithm, _e.g._ `lzo`.
+    pub compressor: Option<String>,
+
+    /// The allocator of the pool, _e.g._ `zsmalloc`.
+    pub zpool: Option<String>,
+
+    /// The largest share of memory the pool may take up, in percent.
+    pub max_pool_percent: Option<u8>,
+
+    /// The memory used by the pool, and the amount of memory compressed into it, in
+    /// kilobytes, if the kernel makes them readable.
+    pub pool_size: Option<u64>,
+    pub stored: Option<u64>,
+}
+
+/// Swap devices and compressed swap in memory, as returned by [`MemoryReadout::swap`].
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SwapInfo {
+    pub devices: Vec<SwapDevice>,
+    pub zram: Vec<ZramDevice>,
+
+    /// The state of zswap, or `None` if the kernel doesn't support it.
+    pub zswap: Option<Zswap>,
+}
+
+/// A NUMA node, as returned by [`MemoryReadout::numa_nodes`]. Amounts are in kilobytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NumaNode {
+    pub id: usize,
+    pub total: u64,
+    pub free: u64,
+    pub used: u64,
+
+    /// The logical CPUs belonging to the node.
+    pub cpus: Vec<usize>,
+
+    /// The relative distance to every node, including itself, in the order the nodes are
+    /// listed. Accessing local memory has a distance of 10.
+    pub distances: Vec<u32>,
+}
+
+/// A pool of huge pages of one size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HugePagePool {
+    /// The size of a page, in kilobytes.
+    pub size: u64,
+
+    /// The number of pages in the pool, and how many of them are free.
+    pub total: u64,
+    pub free: u64,
+
+    /// The number of free pages that are promised to a mapping already.
+    pub reserved: u64,
+
+    /// The number of pages allocated beyond `total` through overcommitting.
+    pub surplus: u64,
+}
+
+/// When the kernel backs memory with transparent huge pages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TransparentHugePages {
+    Always,
+
+    /// Only memory that is marked with `madvise(MADV_HUGEPAGE)`.
+    Madvise,
+    Never,
+}
+
+/// The huge pages of the system, as returned by [`MemoryReadout::huge_pages`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HugePages {
+    /// The pools, ordered by page size.
+    pub pools: Vec<HugePagePool>,
+
+    /// The transparent huge page mode, or `None` if the kernel doesn't support them.
+    pub transparent: Option<TransparentHugePages>,
+}
+
+/// Whether a limit is imposed by the cgroup of the calling process, or is that of the host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LimitSource {
+    Cgroup,
+    Host,
+}
+
+/// The memory available to the calling process, as returned by [`MemoryReadout::limits`].
+/// Amounts are in kilobytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MemoryLimits {
+    /// The memory the process may use, _i.e._ the lowest limit of its cgroup and its
+    /// ancestors, or the memory of the host.
+    pub limit: u64,
+
+    /// The memory in use, by the cgroup or by the host.
+    pub usage: u64,
+
+    /// The swap the process may use, or `None` if it isn't limited.
+    pub swap_limit: Option<u64>,
+
+    pub source: LimitSource,
+}
+
+/// The CPU time available to the calling process, as returned by
+/// [`GeneralReadout::cpu_limits`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuLimits {
+    /// The number of CPUs the process may keep busy, _e.g._ `1.5` for a quota of 150ms every
+    /// 100ms.
+    pub cores: f64,
+
+    /// The logical CPUs the process may run on.
+    pub cpus: Vec<usize>,
+
+    pub source: LimitSource,
+}
+
+/// Counters of virtual memory events since boot, as returned by [`MemoryReadout::vm_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VmStats {
+    /// The number of processes killed by the OOM killer, if the kernel counts them.
+    pub oom_kills: Option<u64>,
+
+    /// The number of page faults that required reading from disk.
+    pub major_faults: u64,
+
+    /// The number of pages read from and written to swap.
+    pub swap_ins: u64,
+    pub swap_outs: u64,
+
+    /// The number of times an allocation had to wait for memory to be reclaimed.
+    pub allocation_stalls: u64,
+}
@@ -1,8 +1,10 @@
+use crate::capabilities::{Capability, ReadoutKind};
 use crate::traits::*;
 use std::collections::HashMap;
 use std::env;
 use std::fs::read_dir;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use winreg::enums::*;
 use winreg::RegKey;
 use wmi::WMIResult;
@@ -20,11 +22,12 @@ use windows::{
 
 impl From<wmi::WMIError> for ReadoutError {
     fn from(e: wmi::WMIError) -> Self {
-        ReadoutError::Other(e.to_string())
+        ReadoutError::failed(ReadoutErrorKind::Other).with_message(e.to_string())
     }
 }
 
 pub struct WindowsBatteryReadout;
+pub struct WindowsSensorReadout;
 
 impl BatteryReadout for WindowsBatteryReadout {
     fn new() -> Self {
@@ -46,9 +49,15 @@ impl BatteryReadout for WindowsBatteryReadout {
     fn status(&self) -> Result<BatteryState, ReadoutError> {
         let power_state = WindowsBatteryReadout::get_power_status()?;
 
+        // The BatteryFlag value that is set while the battery is charging, i.e. bit 3.
+        const BATTERY_FLAG_CHARGING: u8 = 8;
+
         match power_state.ACLineStatus {
             0 => Ok(BatteryState::Discharging),
-            1 => Ok(BatteryState::Charging),
+            1 if power_state.BatteryFlag & BATTERY_FLAG_CHARGING != 0 => Ok(BatteryState::Charging),
+            1 if power_state.BatteryLifePercent == 100 => Ok(BatteryState::Full),
+            1 => Ok(BatteryState::NotCharging),
+            255 => Ok(BatteryState::Unknown),
             a => Err(ReadoutError::Other(format!(
                 "Unexpected value for ac_line_status from win32 api: {a}"
             ))),
@@ -58,6 +67,27 @@ impl BatteryReadout for WindowsBatteryReadout {
     fn health(&self) -> Result<u8, ReadoutError> {
         Err(ReadoutError::NotImplemented)
     }
+
+    fn ac_online(&self) -> Result<bool, ReadoutError> {
+        let power_state = WindowsBatteryReadout::get_power_status()?;
+
+        match power_state.ACLineStatus {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(ReadoutError::MetricNotAvailable),
+        }
+    }
+
+    fn time_to_empty(&self) -> Result<Duration, ReadoutError> {
+        let power_state = WindowsBatteryReadout::get_power_status()?;
+
+        // Windows only estimates the remaining time while running on battery, and reports
+        // u32::MAX when it can't.
+        match (power_state.ACLineStatus, power_state.BatteryLifeTime) {
+            (0, seconds) if seconds != u32::MAX => Ok(Duration::from_secs(seconds as u64)),
+            _ => Err(ReadoutError::MetricNotAvailable),
+        }
+    }
 }
 
 impl WindowsBatteryReadout {
@@ -530,3 +560,32 @@ impl NetworkReadout for WindowsNetworkReadout {
         Err(ReadoutError::NotImplemented)
     }
 }
+
+impl SensorReadout for WindowsSensorReadout {
+    fn new() -> Self {
+        WindowsSensorReadout
+    }
+}
+
+/// The metrics implemented by this backend.
+pub(crate) const CAPABILITIES: &[Capability] = &[
+    Capability::new(ReadoutKind::Battery, "percentage"),
+    Capability::new(ReadoutKind::Battery, "status"),
+    Capability::new(ReadoutKind::Battery, "ac_online"),
+    Capability::new(ReadoutKind::Battery, "time_to_empty"),
+    Capability::new(ReadoutKind::Kernel, "os_release"),
+    Capability::new(ReadoutKind::Kernel, "os_type"),
+    Capability::new(ReadoutKind::Kernel, "pretty_kernel"),
+    Capability::new(ReadoutKind::Memory, "total"),
+    Capability::new(ReadoutKind::Memory, "used"),
+    Capability::new(ReadoutKind::General, "username"),
+    Capability::new(ReadoutKind::General, "hostname"),
+    Capability::new(ReadoutKind::General, "cpu_model_name"),
+    Capability::new(ReadoutKind::General, "uptime"),
+    Capability::new(ReadoutKind::General, "machine"),
+    Capability::new(ReadoutKind::General, "os_name"),
+    Capability::new(ReadoutKind::Product, "vendor"),
+    Capability::new(ReadoutKind::Product, "product"),
+    Capability::new(ReadoutKind::Package, "count_pkgs"),
+    Capability::new(ReadoutKind::Network, "logical_address"),
+];